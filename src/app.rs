@@ -0,0 +1,96 @@
+use std::path::{Path, PathBuf};
+
+use iced::{event, window, Element, Event, Subscription, Task};
+
+use crate::session::SessionState;
+use crate::views::configure::{pick_config_file, HydraSettings, Message};
+use crate::views::welcome;
+
+/// Name the form suggests when saving, looked for in the working directory.
+pub const DEFAULT_CONFIG_FILE: &str = "juno-config.json";
+
+pub enum AppState {
+    /// Shown on first launch, before there is any config to edit.
+    Welcome,
+    Configure(HydraSettings),
+}
+
+impl AppState {
+    /// Opens `config` when one was given on the command line, else the
+    /// config of the previous session.
+    pub fn new(config: Option<PathBuf>) -> (Self, Task<Message>) {
+        if let Some(path) = config {
+            let mut settings = HydraSettings::new();
+            settings.open_config(path);
+            return Self::configure(settings);
+        }
+
+        let has_config = SessionState::load()
+            .last_config_path
+            .is_some_and(|path| path.is_file())
+            || Path::new(DEFAULT_CONFIG_FILE).is_file();
+
+        if has_config {
+            Self::configure(HydraSettings::new())
+        } else {
+            (AppState::Welcome, Task::none())
+        }
+    }
+
+    /// Switches to the form, running the work it does on startup.
+    fn configure(settings: HydraSettings) -> (Self, Task<Message>) {
+        let task = Task::batch([
+            Task::done(Message::CheckHydraNodeBinary),
+            settings.restore_scroll(),
+        ]);
+        (AppState::Configure(settings), task)
+    }
+
+    pub fn update(&mut self, message: Message) -> Task<Message> {
+        if let AppState::Configure(settings) = self {
+            return settings.update(message);
+        }
+
+        let settings = match message {
+            Message::StartFromScratch => HydraSettings::new(),
+            Message::ImportConfig => {
+                let Some(path) = pick_config_file() else {
+                    return Task::none();
+                };
+                let mut settings = HydraSettings::new();
+                settings.open_config(path);
+                settings
+            }
+            Message::UseTemplate(template) => {
+                let mut settings = HydraSettings::new();
+                settings.start_from(template.run_options());
+                settings
+            }
+            Message::WindowCloseRequested(id) => return window::close(id),
+            _ => return Task::none(),
+        };
+
+        let (state, task) = Self::configure(settings);
+        *self = state;
+        task
+    }
+
+    pub fn view(&self) -> Element<Message> {
+        match self {
+            AppState::Welcome => welcome::view(),
+            AppState::Configure(settings) => settings.view(),
+        }
+    }
+
+    pub fn subscription(&self) -> Subscription<Message> {
+        match self {
+            AppState::Welcome => event::listen_with(|event, _status, id| match event {
+                Event::Window(window::Event::CloseRequested) => {
+                    Some(Message::WindowCloseRequested(id))
+                }
+                _ => None,
+            }),
+            AppState::Configure(settings) => settings.subscription(),
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// Juno's own settings, kept apart from the node configurations it edits.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AppConfig {
+    #[serde(default)]
+    pub last_seen_version: Option<String>,
+    /// Path or command name used to run hydra-node.
+    #[serde(default)]
+    pub hydra_node_binary: Option<String>,
+    /// Last directory browsed to, per kind of file.
+    #[serde(default)]
+    pub dialog_dirs: HashMap<DialogPurpose, PathBuf>,
+}
+
+/// What a file dialog is picking, so each kind starts where it was last left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DialogPurpose {
+    Keys,
+    Utxo,
+    Config,
+    Binary,
+    Persistence,
+}
+
+impl AppConfig {
+    /// `~/.config/juno/state.json`, or `None` when there is no home directory.
+    pub fn path() -> Option<PathBuf> {
+        let home = std::env::var_os("HOME")?;
+        Some(
+            PathBuf::from(home)
+                .join(".config")
+                .join("juno")
+                .join("state.json"),
+        )
+    }
+
+    /// Reads the stored settings, falling back to defaults when there are none
+    /// yet or they cannot be read.
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    /// A file dialog starting in the last directory used for `purpose`.
+    pub fn file_dialog(&self, purpose: DialogPurpose) -> rfd::FileDialog {
+        let dialog = rfd::FileDialog::new();
+
+        match self.dialog_dirs.get(&purpose) {
+            Some(dir) if dir.is_dir() => dialog.set_directory(dir),
+            _ => dialog,
+        }
+    }
+
+    /// Remembers where `path` was picked for the next dialog for `purpose`.
+    /// Picked directories are remembered themselves, files by their parent.
+    pub fn remember_dialog_dir(&mut self, purpose: DialogPurpose, path: &Path) {
+        let dir = if path.is_dir() {
+            Some(path)
+        } else {
+            path.parent()
+        };
+        let Some(dir) = dir.filter(|dir| !dir.as_os_str().is_empty()) else {
+            return;
+        };
+
+        self.dialog_dirs.insert(purpose, dir.to_path_buf());
+        if let Err(e) = self.save() {
+            tracing::warn!(error = %e, "failed to save dialog directories");
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Self::path().ok_or("HOME is not set")?;
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
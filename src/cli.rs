@@ -0,0 +1,447 @@
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+
+use crate::app::DEFAULT_CONFIG_FILE;
+use crate::hydra::initial_utxo::validate_reader;
+use crate::hydra::protocol_params::validate_protocol_params;
+use crate::hydra::run_options::{ChainConfig, NetworkId, NodeId, RunOptions};
+use crate::hydra::scripts::known_scripts_tx_id;
+use crate::hydra::validation::Severity;
+
+#[derive(Parser, Debug)]
+#[command(name = "juno", version, about = "Configure and launch a Hydra node")]
+pub struct Cli {
+    /// Print a shell completion script and exit
+    #[arg(long, value_enum, value_name = "SHELL")]
+    pub completions: Option<CompletionShell>,
+
+    /// Configuration file to use for a headless action, or to open in the
+    /// GUI when no action is given
+    #[arg(long, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Print the hydra-node command for --config and exit
+    #[arg(long, requires = "config", conflicts_with_all = ["validate", "export_config"])]
+    pub print_command: bool,
+
+    /// Validate --config and exit with a non-zero code on errors
+    #[arg(long, requires = "config", conflicts_with = "export_config")]
+    pub validate: bool,
+
+    /// Print --config in the current layout and exit
+    #[arg(long, requires = "config")]
+    pub export_config: bool,
+
+    /// Key case of the configuration printed by --export-config
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        requires = "export_config",
+        default_value_t = ConfigFormat::Snake
+    )]
+    pub export_format: ConfigFormat,
+
+    /// Read FILE in any layout Juno accepts, check it and save it to --config,
+    /// or else juno-config.json, in the current layout
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = ["print_command", "validate", "export_config"]
+    )]
+    pub import_config: Option<PathBuf>,
+
+    /// Validate FILE and exit with a non-zero code on errors
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["config", "import_config"])]
+    pub validate_config: Option<PathBuf>,
+
+    /// Write the default configuration to FILE and exit
+    #[arg(long, value_name = "FILE")]
+    pub generate_default_config: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+impl Cli {
+    /// Runs the requested headless action, or returns `None` when the GUI
+    /// should be started instead.
+    pub fn run_headless(self) -> Option<Result<(), Box<dyn Error>>> {
+        if let Some(shell) = self.completions {
+            print_completions(shell);
+            return Some(Ok(()));
+        }
+
+        if let Some(path) = &self.generate_default_config {
+            return Some(generate_default_config(path));
+        }
+
+        if let Some(path) = &self.import_config {
+            let output = self
+                .config
+                .clone()
+                .unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG_FILE));
+            return Some(import_config(path, &output));
+        }
+
+        let headless_command = self.headless_command();
+        self.command.or(headless_command).map(Command::run)
+    }
+
+    /// The configuration for the GUI to open, when no headless action is given.
+    pub fn gui_config(&self) -> Option<PathBuf> {
+        self.config.clone()
+    }
+
+    /// Maps the `--config` flags onto the equivalent subcommand.
+    fn headless_command(&self) -> Option<Command> {
+        if let Some(config) = self.validate_config.clone() {
+            return Some(Command::Validate { config });
+        }
+
+        let config = self.config.clone()?;
+
+        if self.export_config {
+            Some(Command::ExportConfig {
+                config,
+                format: self.export_format,
+            })
+        } else if self.print_command {
+            Some(Command::EmitCommand {
+                config,
+                format: CommandFormat::Shell,
+            })
+        } else if self.validate {
+            Some(Command::Validate { config })
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Print the hydra-node command for a saved configuration
+    EmitCommand {
+        /// Path to a saved configuration file
+        config: PathBuf,
+
+        /// Shape of the printed command
+        #[arg(long, value_enum, default_value_t = CommandFormat::Shell)]
+        format: CommandFormat,
+    },
+
+    /// Validate a saved configuration and the UTxO and protocol parameter
+    /// files it references
+    Validate {
+        /// Path to a saved configuration file
+        config: PathBuf,
+    },
+
+    /// Print a saved configuration in the current layout
+    ExportConfig {
+        /// Path to a saved configuration file
+        config: PathBuf,
+
+        /// Key case of the printed configuration
+        #[arg(long, value_enum, default_value_t = ConfigFormat::Snake)]
+        format: ConfigFormat,
+    },
+
+    /// Write a minimal configuration built from the given flags
+    Generate {
+        /// Identifier of the node
+        #[arg(long)]
+        node_id: String,
+
+        /// Cardano network: `mainnet` or `testnet:<magic>`
+        #[arg(long)]
+        network: NetworkId,
+
+        /// Port the node listens on for peers
+        #[arg(long)]
+        port: Option<u16>,
+
+        /// Path to the Hydra signing key
+        #[arg(long)]
+        signing_key: PathBuf,
+
+        /// Transaction that published the Hydra scripts; defaults to the one
+        /// published for the network, when known
+        #[arg(long)]
+        hydra_scripts_tx_id: Option<String>,
+
+        /// Where to write the generated configuration
+        #[arg(long)]
+        output: PathBuf,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CommandFormat {
+    Shell,
+    JsonArray,
+    /// One argument per line, without the program name
+    Lines,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ConfigFormat {
+    /// snake_case keys, as Juno stores them
+    Snake,
+    /// camelCase keys, as hydra-node names its settings
+    Camel,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CompletionShell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl From<CompletionShell> for Shell {
+    fn from(shell: CompletionShell) -> Self {
+        match shell {
+            CompletionShell::Bash => Shell::Bash,
+            CompletionShell::Zsh => Shell::Zsh,
+            CompletionShell::Fish => Shell::Fish,
+        }
+    }
+}
+
+fn print_completions(shell: CompletionShell) {
+    let mut command = Cli::command();
+    let name = command.get_name().to_string();
+    clap_complete::generate(
+        Shell::from(shell),
+        &mut command,
+        name,
+        &mut std::io::stdout(),
+    );
+}
+
+impl Command {
+    pub fn run(self) -> Result<(), Box<dyn Error>> {
+        match self {
+            Command::EmitCommand { config, format } => emit_command(&config, format),
+            Command::Validate { config } => validate(&config),
+            Command::ExportConfig { config, format } => export_config(&config, format),
+            Command::Generate {
+                node_id,
+                network,
+                port,
+                signing_key,
+                hydra_scripts_tx_id,
+                output,
+            } => generate(
+                node_id,
+                network,
+                port,
+                signing_key,
+                hydra_scripts_tx_id,
+                &output,
+            ),
+        }
+    }
+}
+
+fn emit_command(config: &Path, format: CommandFormat) -> Result<(), Box<dyn Error>> {
+    let run_options = RunOptions::load(config)?;
+
+    match format {
+        CommandFormat::Shell => println!("{}", run_options.to_command_line()),
+        CommandFormat::JsonArray => {
+            let command: Vec<String> = std::iter::once("hydra-node".to_string())
+                .chain(run_options.to_args())
+                .collect();
+            println!("{}", serde_json::to_string(&command)?);
+        }
+        CommandFormat::Lines => print!("{}", run_options.to_args_lines()),
+    }
+
+    Ok(())
+}
+
+/// Prints `config` with keys and layout as Juno saves them, after reading
+/// JSON5 and migrating older versions.
+fn export_config(config: &Path, format: ConfigFormat) -> Result<(), Box<dyn Error>> {
+    println!("{}", exported_config(config, format)?);
+    Ok(())
+}
+
+/// The configuration at `config` as JSON with keys in `format`.
+fn exported_config(config: &Path, format: ConfigFormat) -> Result<String, Box<dyn Error>> {
+    let run_options = RunOptions::load(config)?;
+    let json = match format {
+        ConfigFormat::Snake => run_options.to_snake_case_json()?,
+        ConfigFormat::Camel => run_options.to_camel_case_json()?,
+    };
+    Ok(json)
+}
+
+/// Saves `input` to `output` in the current layout, refusing it when it has
+/// errors.
+fn import_config(input: &Path, output: &Path) -> Result<(), Box<dyn Error>> {
+    let run_options = RunOptions::load(input)?;
+    let mut failed = false;
+
+    for issue in run_options.validate() {
+        eprintln!("{}", issue);
+        failed |= issue.severity == Severity::Error;
+    }
+    if failed {
+        return Err(format!("{} is not valid", input.display()).into());
+    }
+
+    run_options.save(output)?;
+    eprintln!("Imported {} into {}", input.display(), output.display());
+    Ok(())
+}
+
+fn validate(config: &Path) -> Result<(), Box<dyn Error>> {
+    let run_options = RunOptions::load(config)?;
+    let mut failed = false;
+
+    for issue in run_options.validate() {
+        eprintln!("{}", issue);
+        failed |= issue.severity == Severity::Error;
+    }
+
+    if let ChainConfig::Offline(offline_config) = &run_options.chain_config {
+        let utxo_file = &offline_config.initial_utxo_file;
+        let result = std::fs::File::open(utxo_file)
+            .map_err(|e| e.into())
+            .and_then(|file| validate_reader(std::io::BufReader::new(file), |_| {}));
+
+        if let Err(e) = result {
+            eprintln!("error: {}: {}", utxo_file.display(), e);
+            failed = true;
+        }
+    }
+
+    let protocol_parameters = &run_options
+        .ledger_config
+        .cardano_ledger_protocol_parameters_file;
+    if let Err(e) = validate_protocol_params(protocol_parameters) {
+        eprintln!("error: {}: {}", protocol_parameters.display(), e);
+        failed = true;
+    }
+
+    if failed {
+        return Err(format!("{} is not valid", config.display()).into());
+    }
+
+    Ok(())
+}
+
+fn generate(
+    node_id: String,
+    network: NetworkId,
+    port: Option<u16>,
+    signing_key: PathBuf,
+    hydra_scripts_tx_id: Option<String>,
+    output: &Path,
+) -> Result<(), Box<dyn Error>> {
+    let mut run_options = RunOptions {
+        node_id: NodeId(node_id),
+        hydra_signing_key: signing_key,
+        ..RunOptions::default()
+    };
+
+    if let Some(port) = port {
+        run_options.port = port;
+    }
+
+    if let ChainConfig::Direct(config) = &mut run_options.chain_config {
+        config.hydra_scripts_tx_id = hydra_scripts_tx_id
+            .or_else(|| known_scripts_tx_id(&network, None))
+            .unwrap_or_default();
+        config.network_id = network;
+    }
+
+    let errors: Vec<String> = run_options
+        .validate()
+        .into_iter()
+        .filter(|issue| issue.severity == Severity::Error)
+        .map(|issue| issue.message)
+        .collect();
+
+    if !errors.is_empty() {
+        return Err(errors.join("; ").into());
+    }
+
+    run_options.save(output)
+}
+
+fn generate_default_config(output: &Path) -> Result<(), Box<dyn Error>> {
+    let run_options = RunOptions::default();
+    run_options.save(output)?;
+
+    if RunOptions::load(output)? != run_options {
+        return Err(format!("{} does not load back to the defaults", output.display()).into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_path;
+    use std::ffi::OsStr;
+    use std::fs;
+
+    #[test]
+    fn generated_default_config_loads_back_to_the_defaults() {
+        let path = temp_path("default-config.json");
+
+        generate_default_config(&path).unwrap();
+        let loaded = RunOptions::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, RunOptions::default());
+    }
+
+    #[test]
+    fn generate_default_config_flag_writes_the_defaults() {
+        let path = temp_path("default-config-flag.json");
+        let cli = Cli::try_parse_from([
+            OsStr::new("juno"),
+            OsStr::new("--generate-default-config"),
+            path.as_os_str(),
+        ])
+        .unwrap();
+
+        assert!(matches!(cli.run_headless(), Some(Ok(()))));
+        let loaded = RunOptions::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, RunOptions::default());
+    }
+
+    #[test]
+    fn exports_configs_in_either_key_case() {
+        let path = temp_path("export-format.json");
+        RunOptions::default().save(&path).unwrap();
+
+        let snake = exported_config(&path, ConfigFormat::Snake).unwrap();
+        let camel = exported_config(&path, ConfigFormat::Camel).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(snake.contains("\"hydra_signing_key\""), "{}", snake);
+        assert!(camel.contains("\"hydraSigningKey\""), "{}", camel);
+        assert_eq!(camel, RunOptions::default().to_camel_case_json().unwrap());
+    }
+
+    #[test]
+    fn export_format_needs_export_config() {
+        let result =
+            Cli::try_parse_from(["juno", "--config", "a.json", "--export-format", "camel"]);
+
+        assert!(result.is_err());
+    }
+}
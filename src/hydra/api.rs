@@ -0,0 +1,80 @@
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+
+use iced::futures::{SinkExt, StreamExt};
+use tokio_tungstenite::tungstenite::Message as WsMessage;
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+const ABORT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Checks that something answers HTTP on the node's API address. Any HTTP
+/// response counts as reachable, since the API only upgrades to websockets.
+pub async fn probe_api(host: IpAddr, port: u16, tls: bool) -> Result<String, String> {
+    let address = SocketAddr::new(host, port);
+    let url = format!("{}://{}/", if tls { "https" } else { "http" }, address);
+
+    // This only tests reachability, so self-signed node certificates are fine.
+    let client = reqwest::Client::builder()
+        .timeout(PROBE_TIMEOUT)
+        .danger_accept_invalid_certs(true)
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    match client.get(&url).send().await {
+        Ok(response) => Ok(format!("{} answered with {}", address, response.status())),
+        Err(e) if e.is_timeout() => Err(format!(
+            "{} did not answer within {} seconds",
+            address,
+            PROBE_TIMEOUT.as_secs()
+        )),
+        Err(e) if e.is_connect() => Err(format!("Connection to {} was refused", address)),
+        Err(e) => Err(format!("Failed to reach {}: {}", address, e)),
+    }
+}
+
+/// Sends `Abort` to the node's websocket API and returns the first message
+/// the node answers with after its greeting.
+pub async fn send_abort(host: IpAddr, port: u16, tls: bool) -> Result<String, String> {
+    let address = SocketAddr::new(host, port);
+    let url = format!("{}://{}/", if tls { "wss" } else { "ws" }, address);
+
+    let exchange = async {
+        let (mut socket, _) = tokio_tungstenite::connect_async(&url)
+            .await
+            .map_err(|e| format!("Failed to connect to {}: {}", url, e))?;
+
+        socket
+            .send(WsMessage::text(r#"{"tag":"Abort"}"#))
+            .await
+            .map_err(|e| format!("Failed to send Abort: {}", e))?;
+
+        while let Some(message) = socket.next().await {
+            let message = message.map_err(|e| format!("Failed to read the response: {}", e))?;
+
+            if let WsMessage::Text(text) = message {
+                let tag = serde_json::from_str::<serde_json::Value>(&text)
+                    .ok()
+                    .and_then(|json| json.get("tag")?.as_str().map(str::to_string));
+
+                if tag.as_deref() != Some("Greetings") {
+                    return Ok(text.to_string());
+                }
+            }
+        }
+
+        Err(format!(
+            "{} closed the connection without answering",
+            address
+        ))
+    };
+
+    tokio::time::timeout(ABORT_TIMEOUT, exchange)
+        .await
+        .map_err(|_| {
+            format!(
+                "{} did not answer within {} seconds",
+                address,
+                ABORT_TIMEOUT.as_secs()
+            )
+        })?
+}
@@ -0,0 +1,99 @@
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::process::Command;
+
+pub const HYDRA_NODE_BINARY: &str = "hydra-node";
+
+pub const INSTALL_INSTRUCTIONS: &str = "Download a release from \
+    https://github.com/cardano-scaling/hydra/releases and make sure the \
+    hydra-node binary is on your PATH.";
+
+/// Runs `<binary> --version` and returns the reported version string.
+pub fn hydra_node_version(binary: &str) -> Result<String, String> {
+    let output = Command::new(binary)
+        .arg("--version")
+        .output()
+        .map_err(|e| match e.kind() {
+            ErrorKind::NotFound => format!("{} was not found", binary),
+            _ => format!("Failed to run {}: {}", binary, e),
+        })?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "{} --version exited with {}",
+            binary, output.status
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+pub type Version = (u64, u64, u64);
+
+/// The first hydra-node release accepting each flag. Flags not listed here
+/// have been around since the versions Juno supports.
+const FLAG_INTRODUCED_IN: &[(&str, Version)] = &[
+    ("--monitoring-port", (0, 9, 0)),
+    ("--start-chain-from", (0, 9, 0)),
+    ("--contestation-period", (0, 10, 0)),
+    ("--ledger-genesis", (0, 13, 0)),
+    ("--tls-cert", (0, 20, 0)),
+    ("--tls-key", (0, 20, 0)),
+    ("--deposit-deadline", (0, 20, 0)),
+];
+
+/// Finds the `major.minor.patch` version in `hydra-node --version` output,
+/// ignoring any prefix or pre-release suffix.
+pub fn parse_version(output: &str) -> Option<Version> {
+    output.split_whitespace().find_map(|word| {
+        let core = word.trim_start_matches('v').split(['-', '+']).next()?;
+        let mut parts = core.split('.').map(|part| part.parse::<u64>().ok());
+
+        match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(Some(major)), Some(Some(minor)), Some(Some(patch)), None) => {
+                Some((major, minor, patch))
+            }
+            _ => None,
+        }
+    })
+}
+
+/// Describes every flag in `args` that `version` does not understand yet.
+pub fn unsupported_flags(version: Version, args: &[String]) -> Vec<String> {
+    FLAG_INTRODUCED_IN
+        .iter()
+        .filter(|(flag, introduced)| version < *introduced && args.iter().any(|arg| arg == flag))
+        .map(|(flag, (major, minor, patch))| {
+            format!(
+                "{} requires hydra-node {}.{}.{} or newer",
+                flag, major, minor, patch
+            )
+        })
+        .collect()
+}
+
+/// Runs `<binary> --help` and returns the usage text.
+pub fn hydra_node_help(binary: &str) -> Result<String, String> {
+    let output = Command::new(binary)
+        .arg("--help")
+        .output()
+        .map_err(|e| match e.kind() {
+            ErrorKind::NotFound => format!("{} was not found", binary),
+            _ => format!("Failed to run {}: {}", binary, e),
+        })?;
+
+    if !output.status.success() {
+        return Err(format!("{} --help exited with {}", binary, output.status));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Looks `name` up in the directories on `PATH`, like a shell would.
+pub fn find_on_path(name: &str) -> Option<PathBuf> {
+    let path = std::env::var_os("PATH")?;
+
+    std::env::split_paths(&path)
+        .map(|dir| dir.join(name))
+        .find(|candidate| candidate.is_file())
+}
@@ -0,0 +1,382 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+use super::keys::restrict_permissions;
+use super::run_options::{ChainConfig, RunOptions};
+
+/// Name of the configuration inside a bundle.
+pub const BUNDLE_CONFIG: &str = "config.json";
+
+/// Directory inside a bundle that holds the referenced files.
+const BUNDLE_FILES_DIR: &str = "files";
+
+/// Writes `options` and every file it references into a zip at `output`.
+/// Paths in the bundled configuration are rewritten relative to the bundle
+/// root. Returns warnings for references that could not be bundled, such as
+/// node sockets or missing files; those paths are left as they were.
+pub fn export_bundle(options: &RunOptions, output: &Path) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut options = options.clone();
+    let mut warnings = Vec::new();
+    let mut bundled: HashMap<PathBuf, PathBuf> = HashMap::new();
+    let mut files: Vec<(PathBuf, PathBuf)> = Vec::new();
+
+    if let ChainConfig::Direct(config) = &options.chain_config {
+        warnings.push(format!(
+            "Node socket {} is not bundled",
+            config.node_socket.display()
+        ));
+    }
+
+    for path in referenced_files(&mut options) {
+        if path.as_os_str().is_empty() {
+            continue;
+        }
+
+        if let Some(entry) = bundled.get(path.as_path()) {
+            *path = entry.clone();
+            continue;
+        }
+
+        if !path.is_file() {
+            warnings.push(format!(
+                "{} is not a file and is not bundled",
+                path.display()
+            ));
+            continue;
+        }
+
+        let file_name = path
+            .file_name()
+            .map_or_else(|| "file".into(), |name| name.to_string_lossy().into_owned());
+        let entry = Path::new(BUNDLE_FILES_DIR).join(format!("{}-{}", files.len(), file_name));
+
+        bundled.insert(path.clone(), entry.clone());
+        files.push((path.clone(), entry.clone()));
+        *path = entry;
+    }
+
+    let mut zip = ZipWriter::new(fs::File::create(output)?);
+    let zip_options = SimpleFileOptions::default();
+
+    zip.start_file(BUNDLE_CONFIG, zip_options)?;
+    zip.write_all(options.to_snake_case_json()?.as_bytes())?;
+
+    for (source, entry) in &files {
+        let contents =
+            fs::read(source).map_err(|e| format!("Failed to read {}: {}", source.display(), e))?;
+        zip.start_file(entry.to_string_lossy(), file_options(source, zip_options))?;
+        zip.write_all(&contents)?;
+    }
+
+    zip.finish()?;
+    tracing::info!(
+        path = %output.display(),
+        files = files.len(),
+        "exported bundle"
+    );
+
+    Ok(warnings)
+}
+
+/// Extracts the bundle at `archive` into `target` and returns the path of
+/// the extracted configuration together with its options. Bundled relative
+/// paths are rewritten to absolute paths under `target` and the rewritten
+/// configuration is saved in place. Entries that would land outside `target`
+/// are rejected before anything is written.
+pub fn import_bundle(
+    archive: &Path,
+    target: &Path,
+) -> Result<(PathBuf, RunOptions), Box<dyn Error>> {
+    let mut zip = ZipArchive::new(fs::File::open(archive)?)?;
+
+    for idx in 0..zip.len() {
+        let file = zip.by_index(idx)?;
+        if file.enclosed_name().is_none() {
+            return Err(
+                format!("Bundle entry {} escapes the target directory", file.name()).into(),
+            );
+        }
+    }
+
+    fs::create_dir_all(target)?;
+    let target = fs::canonicalize(target)?;
+
+    for idx in 0..zip.len() {
+        let mut file = zip.by_index(idx)?;
+        let Some(name) = file.enclosed_name() else {
+            continue;
+        };
+        let destination = target.join(name);
+
+        if file.is_dir() {
+            fs::create_dir_all(&destination)?;
+            continue;
+        }
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        io::copy(&mut file, &mut fs::File::create(&destination)?)?;
+        restore_mode(&destination, file.unix_mode())?;
+    }
+
+    let config_path = target.join(BUNDLE_CONFIG);
+    let mut options = RunOptions::load(&config_path)
+        .map_err(|e| format!("Bundle has no readable {}: {}", BUNDLE_CONFIG, e))?;
+
+    for path in referenced_files(&mut options) {
+        if !path.as_os_str().is_empty() && path.is_relative() {
+            *path = target.join(&*path);
+        }
+    }
+
+    // Bundles written by other tools may not carry modes, and extracted
+    // signing keys must never be left readable by others.
+    for path in secret_files(&options) {
+        if path.starts_with(&target) && path.is_file() {
+            restrict_permissions(path)?;
+        }
+    }
+
+    options.save(&config_path)?;
+    tracing::info!(
+        bundle = %archive.display(),
+        target = %target.display(),
+        "imported bundle"
+    );
+
+    Ok((config_path, options))
+}
+
+/// Keeps the permissions of `source` on its bundle entry, so keys that are
+/// only readable by their owner stay that way.
+#[cfg(unix)]
+fn file_options(source: &Path, options: SimpleFileOptions) -> SimpleFileOptions {
+    use std::os::unix::fs::PermissionsExt;
+
+    match fs::metadata(source) {
+        Ok(metadata) => options.unix_permissions(metadata.permissions().mode() & 0o777),
+        Err(_) => options,
+    }
+}
+
+#[cfg(not(unix))]
+fn file_options(_source: &Path, options: SimpleFileOptions) -> SimpleFileOptions {
+    options
+}
+
+/// Gives an extracted file the permissions recorded in its bundle entry.
+#[cfg(unix)]
+fn restore_mode(path: &Path, mode: Option<u32>) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    match mode {
+        Some(mode) => fs::set_permissions(path, fs::Permissions::from_mode(mode & 0o777)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(not(unix))]
+fn restore_mode(_path: &Path, _mode: Option<u32>) -> io::Result<()> {
+    Ok(())
+}
+
+/// The signing keys and TLS key in `options`.
+fn secret_files(options: &RunOptions) -> Vec<&PathBuf> {
+    let mut paths: Vec<&PathBuf> = options.tls_key_path.iter().collect();
+    paths.push(&options.hydra_signing_key);
+
+    if let ChainConfig::Direct(config) = &options.chain_config {
+        paths.push(&config.cardano_signing_key);
+    }
+    paths
+}
+
+/// Every file path in `options` that belongs in a bundle. The persistence
+/// directory and node socket are local to a machine and left out.
+fn referenced_files(options: &mut RunOptions) -> Vec<&mut PathBuf> {
+    let mut paths: Vec<&mut PathBuf> = Vec::new();
+
+    paths.extend(options.tls_cert_path.iter_mut());
+    paths.extend(options.tls_key_path.iter_mut());
+    paths.push(&mut options.hydra_signing_key);
+    paths.extend(options.hydra_verification_keys.iter_mut());
+
+    match &mut options.chain_config {
+        ChainConfig::Offline(config) => {
+            paths.push(&mut config.initial_utxo_file);
+            paths.extend(config.ledger_genesis_file.iter_mut());
+        }
+        ChainConfig::Direct(config) => {
+            paths.push(&mut config.cardano_signing_key);
+            paths.extend(config.cardano_verification_keys.iter_mut());
+        }
+    }
+
+    paths.push(
+        &mut options
+            .ledger_config
+            .cardano_ledger_protocol_parameters_file,
+    );
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hydra::run_options::{DirectChainConfig, NodeId};
+    use crate::test_util::temp_dir;
+
+    /// Writes a zip at `path` holding `entries` as `(name, mode, contents)`.
+    fn write_zip(path: &Path, entries: &[(&str, u32, &str)]) {
+        let mut zip = ZipWriter::new(fs::File::create(path).unwrap());
+        for (name, mode, contents) in entries {
+            let options = SimpleFileOptions::default().unix_permissions(*mode);
+            zip.start_file(*name, options).unwrap();
+            zip.write_all(contents.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    /// Writes a file named `name` into `dir`, holding its own name.
+    fn write_file(dir: &Path, name: &str) -> PathBuf {
+        let path = dir.join(name);
+        fs::write(&path, name).unwrap();
+        path
+    }
+
+    #[test]
+    fn export_then_import_yields_equivalent_options() {
+        let dir = temp_dir("bundle-round-trip");
+        let mut options = RunOptions {
+            node_id: NodeId("alice".to_string()),
+            port: 5010,
+            tls_cert_path: Some(write_file(&dir, "alice.crt")),
+            tls_key_path: Some(write_file(&dir, "alice.key")),
+            hydra_signing_key: write_file(&dir, "alice.sk"),
+            hydra_verification_keys: vec![write_file(&dir, "bob.vk"), write_file(&dir, "carol.vk")],
+            chain_config: ChainConfig::Direct(DirectChainConfig {
+                cardano_signing_key: write_file(&dir, "alice.cardano.sk"),
+                cardano_verification_keys: vec![write_file(&dir, "bob.cardano.vk")],
+                ..DirectChainConfig::default()
+            }),
+            ..RunOptions::default()
+        };
+        options
+            .ledger_config
+            .cardano_ledger_protocol_parameters_file = write_file(&dir, "protocol-parameters.json");
+        let archive = dir.join("bundle.zip");
+
+        let warnings = export_bundle(&options, &archive).unwrap();
+        let (config_path, imported) = import_bundle(&archive, &dir.join("target")).unwrap();
+
+        assert_eq!(warnings, ["Node socket node.socket is not bundled"]);
+        let target = fs::canonicalize(dir.join("target")).unwrap();
+        let imported_paths: Vec<PathBuf> = referenced_files(&mut imported.clone())
+            .into_iter()
+            .map(|path| path.to_path_buf())
+            .collect();
+        let mut expected = options.clone();
+        let original_paths = referenced_files(&mut expected);
+        assert_eq!(original_paths.len(), 8);
+        assert_eq!(imported_paths.len(), original_paths.len());
+        for (path, imported_path) in original_paths.into_iter().zip(imported_paths) {
+            assert!(
+                imported_path.starts_with(&target),
+                "{}",
+                imported_path.display()
+            );
+            assert_eq!(fs::read(&imported_path).unwrap(), fs::read(&*path).unwrap());
+            *path = imported_path;
+        }
+        assert_eq!(imported, expected);
+        assert_eq!(RunOptions::load(&config_path).unwrap(), imported);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rejects_entries_outside_the_target() {
+        let dir = temp_dir("zip-slip");
+        let archive = dir.join("bundle.zip");
+        let config = RunOptions::default().to_snake_case_json().unwrap();
+        write_zip(
+            &archive,
+            &[
+                (BUNDLE_CONFIG, 0o644, &config),
+                ("../escaped.txt", 0o644, "outside"),
+            ],
+        );
+
+        let result = import_bundle(&archive, &dir.join("target"));
+
+        let error = result.unwrap_err().to_string();
+        assert!(error.contains("escapes the target directory"), "{}", error);
+        assert!(!dir.join("escaped.txt").exists());
+        assert!(!dir.join("target").join(BUNDLE_CONFIG).exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    fn mode(path: &Path) -> u32 {
+        use std::os::unix::fs::PermissionsExt;
+
+        fs::metadata(path).unwrap().permissions().mode() & 0o777
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn keeps_file_modes_through_a_bundle() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = temp_dir("bundle-modes");
+        let signing_key = dir.join("hydra.sk");
+        let verification_key = dir.join("hydra.vk");
+        fs::write(&signing_key, "sk").unwrap();
+        fs::write(&verification_key, "vk").unwrap();
+        fs::set_permissions(&signing_key, fs::Permissions::from_mode(0o600)).unwrap();
+        fs::set_permissions(&verification_key, fs::Permissions::from_mode(0o644)).unwrap();
+
+        let options = RunOptions {
+            hydra_signing_key: signing_key,
+            hydra_verification_keys: vec![verification_key],
+            ..RunOptions::default()
+        };
+        let archive = dir.join("bundle.zip");
+        export_bundle(&options, &archive).unwrap();
+
+        let (_, imported) = import_bundle(&archive, &dir.join("target")).unwrap();
+
+        assert_eq!(mode(&imported.hydra_signing_key), 0o600);
+        assert_eq!(mode(&imported.hydra_verification_keys[0]), 0o644);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn restricts_signing_keys_bundled_without_modes() {
+        let dir = temp_dir("bundle-open-key");
+        let options = RunOptions {
+            hydra_signing_key: "files/0-hydra.sk".into(),
+            ..RunOptions::default()
+        };
+        let config = options.to_snake_case_json().unwrap();
+        let archive = dir.join("bundle.zip");
+        write_zip(
+            &archive,
+            &[
+                (BUNDLE_CONFIG, 0o644, &config),
+                ("files/0-hydra.sk", 0o644, "sk"),
+            ],
+        );
+
+        let (_, imported) = import_bundle(&archive, &dir.join("target")).unwrap();
+
+        assert_eq!(mode(&imported.hydra_signing_key), 0o600);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,130 @@
+use std::fmt;
+
+/// Bech32 data characters, indexed by their 5-bit value.
+const BECH32_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+const BECH32_CHECKSUM_LENGTH: usize = 6;
+
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Shelley address kinds by the header byte of CIP-19, plus Byron
+/// bootstrap addresses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressType {
+    EnterpriseMainnet,
+    EnterpriseTestnet,
+    BaseMainnet,
+    BaseTestnet,
+    StakeMainnet,
+    StakeTestnet,
+    Byron,
+    Unknown,
+}
+
+impl AddressType {
+    /// Whether outputs can be locked to addresses of this type.
+    pub fn can_hold_funds(self) -> bool {
+        !matches!(
+            self,
+            AddressType::StakeMainnet | AddressType::StakeTestnet | AddressType::Unknown
+        )
+    }
+}
+
+impl fmt::Display for AddressType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AddressType::EnterpriseMainnet => write!(f, "Enterprise (mainnet)"),
+            AddressType::EnterpriseTestnet => write!(f, "Enterprise (testnet)"),
+            AddressType::BaseMainnet => write!(f, "Base (mainnet)"),
+            AddressType::BaseTestnet => write!(f, "Base (testnet)"),
+            AddressType::StakeMainnet => write!(f, "Stake (mainnet)"),
+            AddressType::StakeTestnet => write!(f, "Stake (testnet)"),
+            AddressType::Byron => write!(f, "Byron"),
+            AddressType::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
+/// Classifies `addr` by the header byte of its Bech32 payload. Byron
+/// addresses are Base58 rather than Bech32 and are recognised by their
+/// prefix. Anything that fails to decode, including a bad checksum, is
+/// `Unknown`.
+pub fn detect_address_type(addr: &str) -> AddressType {
+    let addr = addr.trim();
+
+    if is_byron(addr) {
+        return AddressType::Byron;
+    }
+
+    let Some(header) = bech32_header(addr) else {
+        return AddressType::Unknown;
+    };
+    let mainnet = header & 0x0f == 1;
+
+    match (header >> 4, mainnet) {
+        (0..=3, true) => AddressType::BaseMainnet,
+        (0..=3, false) => AddressType::BaseTestnet,
+        (6 | 7, true) => AddressType::EnterpriseMainnet,
+        (6 | 7, false) => AddressType::EnterpriseTestnet,
+        (14 | 15, true) => AddressType::StakeMainnet,
+        (14 | 15, false) => AddressType::StakeTestnet,
+        _ => AddressType::Unknown,
+    }
+}
+
+fn is_byron(addr: &str) -> bool {
+    (addr.starts_with("Ae2") || addr.starts_with("DdzFF"))
+        && addr.chars().all(|c| BASE58_ALPHABET.contains(c))
+}
+
+/// The first payload byte of a Bech32 string with a valid checksum.
+fn bech32_header(addr: &str) -> Option<u8> {
+    // Bech32 is case insensitive, but mixing cases is not allowed.
+    if addr.chars().any(|c| c.is_ascii_uppercase()) && addr.chars().any(|c| c.is_ascii_lowercase())
+    {
+        return None;
+    }
+    let addr = addr.to_ascii_lowercase();
+
+    let (hrp, data) = addr.rsplit_once('1')?;
+    if hrp.is_empty() || !hrp.bytes().all(|b| (33..=126).contains(&b)) {
+        return None;
+    }
+
+    let data = data
+        .bytes()
+        .map(|b| BECH32_CHARSET.iter().position(|&c| c == b).map(|v| v as u8))
+        .collect::<Option<Vec<u8>>>()?;
+    if data.len() < BECH32_CHECKSUM_LENGTH + 2 {
+        return None;
+    }
+
+    let hrp_expanded = hrp
+        .bytes()
+        .map(|b| b >> 5)
+        .chain([0])
+        .chain(hrp.bytes().map(|b| b & 0x1f));
+    if bech32_polymod(hrp_expanded.chain(data.iter().copied())) != 1 {
+        return None;
+    }
+
+    // The payload is packed into 5-bit groups; its first byte spans the
+    // first two.
+    Some((data[0] << 3) | (data[1] >> 2))
+}
+
+fn bech32_polymod(values: impl Iterator<Item = u8>) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+
+    values.fold(1, |checksum, value| {
+        let top = checksum >> 25;
+        let checksum = ((checksum & 0x1ffffff) << 5) ^ u32::from(value);
+
+        GENERATOR
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| (top >> idx) & 1 == 1)
+            .fold(checksum, |checksum, (_, generator)| checksum ^ generator)
+    })
+}
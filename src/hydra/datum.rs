@@ -0,0 +1,166 @@
+use serde_json::Value;
+
+/// Renders Plutus data in the detailed JSON schema (`constructor`/`fields`,
+/// `list`, `map`, `int`, `bytes`) as an indented tree. Anything else is
+/// pretty-printed as plain JSON.
+pub fn format_plutus_data(v: &Value) -> String {
+    let mut out = String::new();
+    write_node(v, 0, &mut out);
+    out
+}
+
+/// How a piece of rendered Plutus data is highlighted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Highlight {
+    Plain,
+    /// A node kind such as `Constr`, `List` or `Map`, and the `=>` of a map.
+    Node,
+    Int,
+    Bytes,
+}
+
+/// Splits the lines of `format_plutus_data` output into highlighted pieces.
+/// Plain JSON lines are kept as a single plain piece.
+pub fn highlight(formatted: &str) -> Vec<Vec<(Highlight, String)>> {
+    formatted
+        .lines()
+        .map(|line| {
+            let body = line.trim_start();
+            let indent = &line[..line.len() - body.len()];
+            let (node, rest) = body.split_once(' ').unwrap_or((body, ""));
+            let rest_highlight = match node {
+                "Constr" | "List" | "Map" | "=>" => Highlight::Plain,
+                "Int" => Highlight::Int,
+                "Bytes" => Highlight::Bytes,
+                _ => return vec![(Highlight::Plain, line.to_string())],
+            };
+
+            let mut pieces = vec![
+                (Highlight::Plain, indent.to_string()),
+                (Highlight::Node, node.to_string()),
+            ];
+            if !rest.is_empty() {
+                pieces.push((rest_highlight, format!(" {}", rest)));
+            }
+            pieces
+        })
+        .collect()
+}
+
+fn write_node(v: &Value, depth: usize, out: &mut String) {
+    let indent = "  ".repeat(depth);
+
+    if let (Some(constructor), Some(Value::Array(fields))) = (v.get("constructor"), v.get("fields"))
+    {
+        out.push_str(&format!("{}Constr {}\n", indent, constructor));
+        for field in fields {
+            write_node(field, depth + 1, out);
+        }
+    } else if let Some(Value::Array(items)) = v.get("list") {
+        out.push_str(&format!("{}List [{}]\n", indent, items.len()));
+        for item in items {
+            write_node(item, depth + 1, out);
+        }
+    } else if let Some(Value::Array(entries)) = v.get("map") {
+        out.push_str(&format!("{}Map {{{}}}\n", indent, entries.len()));
+        for entry in entries {
+            match (entry.get("k"), entry.get("v")) {
+                (Some(key), Some(value)) => {
+                    write_node(key, depth + 1, out);
+                    out.push_str(&format!("{}  =>\n", indent));
+                    write_node(value, depth + 2, out);
+                }
+                _ => write_json(entry, depth + 1, out),
+            }
+        }
+    } else if let Some(int) = v.get("int") {
+        out.push_str(&format!("{}Int {}\n", indent, int));
+    } else if let Some(Value::String(bytes)) = v.get("bytes") {
+        if bytes.is_empty() {
+            out.push_str(&format!("{}Bytes (empty)\n", indent));
+        } else {
+            out.push_str(&format!("{}Bytes #{}\n", indent, bytes));
+        }
+    } else {
+        write_json(v, depth, out);
+    }
+}
+
+fn write_json(v: &Value, depth: usize, out: &mut String) {
+    let indent = "  ".repeat(depth);
+    let json = serde_json::to_string_pretty(v).unwrap_or_else(|_| v.to_string());
+
+    for line in json.lines() {
+        out.push_str(&indent);
+        out.push_str(line);
+        out.push('\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn formats_nested_constructors() {
+        let datum = json!({
+            "constructor": 0,
+            "fields": [
+                { "int": 42 },
+                { "constructor": 1, "fields": [{ "list": [{ "int": 1 }, { "int": 2 }] }] }
+            ]
+        });
+
+        assert_eq!(
+            format_plutus_data(&datum),
+            "Constr 0\n  Int 42\n  Constr 1\n    List [2]\n      Int 1\n      Int 2\n"
+        );
+    }
+
+    #[test]
+    fn formats_byte_strings() {
+        let datum = json!({
+            "map": [
+                { "k": { "bytes": "deadbeef" }, "v": { "bytes": "" } }
+            ]
+        });
+
+        assert_eq!(
+            format_plutus_data(&datum),
+            "Map {1}\n  Bytes #deadbeef\n  =>\n    Bytes (empty)\n"
+        );
+    }
+
+    #[test]
+    fn pretty_prints_anything_else_as_json() {
+        let datum = json!({ "constructor": 0, "fields": [{ "note": "hi" }] });
+
+        assert_eq!(
+            format_plutus_data(&datum),
+            "Constr 0\n  {\n    \"note\": \"hi\"\n  }\n"
+        );
+    }
+
+    #[test]
+    fn highlights_node_kinds_and_values() {
+        let lines = highlight("Constr 0\n  Bytes #ab\n  {\n");
+
+        assert_eq!(
+            lines,
+            vec![
+                vec![
+                    (Highlight::Plain, String::new()),
+                    (Highlight::Node, "Constr".to_string()),
+                    (Highlight::Plain, " 0".to_string())
+                ],
+                vec![
+                    (Highlight::Plain, "  ".to_string()),
+                    (Highlight::Node, "Bytes".to_string()),
+                    (Highlight::Bytes, " #ab".to_string())
+                ],
+                vec![(Highlight::Plain, "  {".to_string())]
+            ]
+        );
+    }
+}
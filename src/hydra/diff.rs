@@ -0,0 +1,325 @@
+use std::fmt;
+use std::net::IpAddr;
+use std::path::PathBuf;
+
+use super::run_options::{
+    ChainConfig, Host, HydraProtocolParams, LedgerConfig, NodeId, RunOptions, Verbosity,
+};
+
+/// How a field's value reads in a diff.
+trait DiffValue {
+    fn diff_value(&self) -> String;
+}
+
+macro_rules! display_diff_value {
+    ($($ty:ty),*) => {
+        $(
+            impl DiffValue for $ty {
+                fn diff_value(&self) -> String {
+                    self.to_string()
+                }
+            }
+        )*
+    };
+}
+
+display_diff_value!(NodeId, Host, IpAddr, u16);
+
+impl DiffValue for String {
+    fn diff_value(&self) -> String {
+        if self.is_empty() {
+            "(not set)".to_string()
+        } else {
+            self.clone()
+        }
+    }
+}
+
+impl DiffValue for PathBuf {
+    fn diff_value(&self) -> String {
+        self.display().to_string().diff_value()
+    }
+}
+
+impl<T: DiffValue> DiffValue for Option<T> {
+    fn diff_value(&self) -> String {
+        self.as_ref()
+            .map_or("(not set)".to_string(), DiffValue::diff_value)
+    }
+}
+
+impl<T: DiffValue> DiffValue for Vec<T> {
+    fn diff_value(&self) -> String {
+        if self.is_empty() {
+            return "(none)".to_string();
+        }
+        self.iter()
+            .map(DiffValue::diff_value)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+impl DiffValue for Verbosity {
+    fn diff_value(&self) -> String {
+        match self {
+            Verbosity::Quiet => "Quiet".to_string(),
+            Verbosity::Verbose => "Verbose".to_string(),
+        }
+    }
+}
+
+impl DiffValue for ChainConfig {
+    fn diff_value(&self) -> String {
+        match self {
+            ChainConfig::Offline(config) => format!(
+                "Offline; initial UTxO {}; ledger genesis {}",
+                config.initial_utxo_file.diff_value(),
+                config.ledger_genesis_file.diff_value()
+            ),
+            ChainConfig::Direct(config) => format!(
+                "Direct; network {}; node socket {}; scripts tx {}; signing key {}; \
+                 verification keys {}; start from {}; contestation period {}s; \
+                 deposit deadline {}s",
+                config.network_id,
+                config.node_socket.diff_value(),
+                config.hydra_scripts_tx_id.diff_value(),
+                config.cardano_signing_key.diff_value(),
+                config.cardano_verification_keys.diff_value(),
+                config.start_chain_from.diff_value(),
+                config.contestation_period,
+                config.deposit_deadline
+            ),
+        }
+    }
+}
+
+impl DiffValue for LedgerConfig {
+    fn diff_value(&self) -> String {
+        self.cardano_ledger_protocol_parameters_file.diff_value()
+    }
+}
+
+impl DiffValue for HydraProtocolParams {
+    fn diff_value(&self) -> String {
+        let seconds =
+            |value: Option<u64>| value.map_or("(not set)".to_string(), |v| format!("{}s", v));
+        format!(
+            "minimum contestation period {}; maximum tx validity range {}",
+            seconds(self.minimum_contestation_period),
+            seconds(self.maximum_tx_validity_range)
+        )
+    }
+}
+
+macro_rules! run_options_diff {
+    ($($field:ident: $ty:ty),* $(,)?) => {
+        /// Field-by-field difference between two `RunOptions`, holding
+        /// `(old, new)` for every field that changed.
+        #[derive(Debug, Clone, Default, Eq, PartialEq)]
+        pub struct RunOptionsDiff {
+            $(pub $field: Option<($ty, $ty)>,)*
+        }
+
+        impl RunOptions {
+            pub fn diff(&self, other: &RunOptions) -> RunOptionsDiff {
+                RunOptionsDiff {
+                    $($field: (self.$field != other.$field)
+                        .then(|| (self.$field.clone(), other.$field.clone())),)*
+                }
+            }
+        }
+
+        impl RunOptionsDiff {
+            /// `(field, before, after)` for every changed field, in declaration order,
+            /// with the values formatted for reading.
+            pub fn rows(&self) -> Vec<(&'static str, String, String)> {
+                let mut rows = Vec::new();
+                $(
+                    if let Some((old, new)) = &self.$field {
+                        rows.push((stringify!($field), old.diff_value(), new.diff_value()));
+                    }
+                )*
+                rows
+            }
+
+            /// Copies the new value of every changed field named in `fields`
+            /// into `options`.
+            pub fn apply_fields(&self, options: &mut RunOptions, fields: &[&str]) {
+                $(
+                    if let Some((_, new)) = &self.$field {
+                        if fields.contains(&stringify!($field)) {
+                            options.$field = new.clone();
+                        }
+                    }
+                )*
+            }
+        }
+    };
+}
+
+run_options_diff! {
+    verbosity: Verbosity,
+    node_id: NodeId,
+    host: IpAddr,
+    port: u16,
+    peers: Vec<Host>,
+    api_host: IpAddr,
+    api_port: u16,
+    tls_cert_path: Option<PathBuf>,
+    tls_key_path: Option<PathBuf>,
+    monitoring_port: Option<u16>,
+    hydra_signing_key: PathBuf,
+    hydra_verification_keys: Vec<PathBuf>,
+    persistence_dir: PathBuf,
+    chain_config: ChainConfig,
+    ledger_config: LedgerConfig,
+    hydra_protocol_params: HydraProtocolParams,
+    extra_args: Vec<String>,
+}
+
+impl RunOptionsDiff {
+    pub fn len(&self) -> usize {
+        self.rows().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl fmt::Display for RunOptionsDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = self.rows();
+        let field_width = rows
+            .iter()
+            .map(|(field, _, _)| field.len())
+            .chain(["field".len()])
+            .max()
+            .unwrap_or_default();
+        let before_width = rows
+            .iter()
+            .map(|(_, before, _)| before.len())
+            .chain(["before".len()])
+            .max()
+            .unwrap_or_default();
+
+        writeln!(
+            f,
+            "{:<field_width$}  {:<before_width$}  after",
+            "field", "before"
+        )?;
+        for (field, before, after) in rows {
+            writeln!(
+                f,
+                "{:<field_width$}  {:<before_width$}  {}",
+                field, before, after
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edited() -> RunOptions {
+        RunOptions {
+            node_id: NodeId("bob".to_string()),
+            port: 5002,
+            monitoring_port: Some(6001),
+            ..RunOptions::default()
+        }
+    }
+
+    #[test]
+    fn identical_options_have_no_diff() {
+        let diff = RunOptions::default().diff(&RunOptions::default());
+
+        assert!(diff.is_empty());
+        assert_eq!(diff, RunOptionsDiff::default());
+    }
+
+    #[test]
+    fn rows_list_changed_fields_in_declaration_order() {
+        let diff = RunOptions::default().diff(&edited());
+        let fields: Vec<&str> = diff.rows().into_iter().map(|(field, _, _)| field).collect();
+
+        assert_eq!(fields, ["node_id", "port", "monitoring_port"]);
+        assert_eq!(diff.len(), 3);
+        assert_eq!(diff.port, Some((5001, 5002)));
+    }
+
+    #[test]
+    fn applying_every_field_yields_the_new_options() {
+        let current = RunOptions::default();
+        let diff = current.diff(&edited());
+
+        let mut options = current.clone();
+        diff.apply_fields(&mut options, &["node_id", "port", "monitoring_port"]);
+
+        assert_eq!(options, edited());
+    }
+
+    #[test]
+    fn applies_only_the_named_fields() {
+        let current = RunOptions::default();
+        let diff = current.diff(&edited());
+
+        let mut options = current.clone();
+        diff.apply_fields(&mut options, &["port", "persistence_dir"]);
+
+        assert_eq!(
+            options,
+            RunOptions {
+                port: 5002,
+                ..current
+            }
+        );
+    }
+
+    #[test]
+    fn rows_show_values_as_users_write_them() {
+        let incoming = RunOptions {
+            peers: vec![
+                "10.0.0.2:5001".parse().unwrap(),
+                "10.0.0.3:5001".parse().unwrap(),
+            ],
+            tls_cert_path: Some("tls/cert.pem".into()),
+            ..edited()
+        };
+        let rows = RunOptions::default().diff(&incoming).rows();
+        let row = |field: &str| {
+            rows.iter()
+                .find(|(name, _, _)| *name == field)
+                .map(|(_, before, after)| (before.as_str(), after.as_str()))
+        };
+
+        assert_eq!(row("node_id"), Some(("hydra-node-1", "bob")));
+        assert_eq!(row("port"), Some(("5001", "5002")));
+        assert_eq!(row("monitoring_port"), Some(("(not set)", "6001")));
+        assert_eq!(
+            row("peers"),
+            Some(("(none)", "10.0.0.2:5001, 10.0.0.3:5001"))
+        );
+        assert_eq!(row("tls_cert_path"), Some(("(not set)", "tls/cert.pem")));
+    }
+
+    #[test]
+    fn chain_config_rows_name_the_mode() {
+        let offline = RunOptions {
+            chain_config: ChainConfig::Offline(Default::default()),
+            ..RunOptions::default()
+        };
+        let rows = RunOptions::default().diff(&offline).rows();
+
+        let (_, before, after) = &rows[0];
+        assert!(before.starts_with("Direct; network 42; "), "{}", before);
+        assert_eq!(
+            after,
+            "Offline; initial UTxO utxo.json; ledger genesis (not set)"
+        );
+    }
+}
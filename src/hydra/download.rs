@@ -0,0 +1,202 @@
+use std::io::{Cursor, Read};
+use std::path::{Path, PathBuf};
+
+use iced::futures::channel::mpsc;
+use iced::futures::{SinkExt, Stream, StreamExt};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+const LATEST_RELEASE_URL: &str =
+    "https://api.github.com/repos/cardano-scaling/hydra/releases/latest";
+
+#[derive(Debug, Clone)]
+pub enum DownloadEvent {
+    Progress(f32),
+    Complete(Result<PathBuf, String>),
+}
+
+#[derive(Deserialize)]
+struct Release {
+    html_url: String,
+    assets: Vec<Asset>,
+}
+
+#[derive(Deserialize)]
+struct Asset {
+    name: String,
+    browser_download_url: String,
+    /// `sha256:<hex>`, computed by GitHub for assets uploaded since mid-2025.
+    #[serde(default)]
+    digest: Option<String>,
+}
+
+/// Downloads the latest `hydra-node` release for this platform into
+/// `~/.local/bin`, reporting progress as a fraction between 0 and 1.
+pub fn download_hydra_node() -> impl Stream<Item = DownloadEvent> {
+    iced::stream::channel(16, |mut output| async move {
+        let result = download(&mut output).await;
+        let _ = output.send(DownloadEvent::Complete(result)).await;
+    })
+}
+
+async fn download(output: &mut mpsc::Sender<DownloadEvent>) -> Result<PathBuf, String> {
+    let client = reqwest::Client::builder()
+        .user_agent("juno")
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let release: Release = client
+        .get(LATEST_RELEASE_URL)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| format!("Failed to fetch the latest release: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to read the latest release: {}", e))?;
+
+    let platform = platform()?;
+    let archive = release
+        .assets
+        .iter()
+        .find(|asset| asset.name.contains(platform) && asset.name.ends_with(".zip"))
+        .ok_or_else(|| format!("The latest release has no archive for {}", platform))?;
+    let expected = expected_digest(&client, &release, archive).await?;
+
+    let response = client
+        .get(&archive.browser_download_url)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| format!("Failed to download {}: {}", archive.name, e))?;
+
+    let total = response.content_length();
+    let mut bytes = Vec::new();
+    let mut chunks = response.bytes_stream();
+
+    while let Some(chunk) = chunks.next().await {
+        let chunk = chunk.map_err(|e| format!("Failed to download {}: {}", archive.name, e))?;
+        bytes.extend_from_slice(&chunk);
+
+        if let Some(total) = total {
+            let _ = output
+                .send(DownloadEvent::Progress(bytes.len() as f32 / total as f32))
+                .await;
+        }
+    }
+
+    let actual = hex_digest(&bytes);
+    if actual != expected {
+        return Err(format!(
+            "Checksum mismatch for {}: expected {}, got {}",
+            archive.name, expected, actual
+        ));
+    }
+
+    let binary = extract_hydra_node(bytes)?;
+    install(&binary)
+}
+
+/// The SHA-256 of `archive`, from a checksum file in the release when there is
+/// one and otherwise from the digest GitHub records for the asset. Without
+/// either the archive cannot be verified, so it is not installed.
+async fn expected_digest(
+    client: &reqwest::Client,
+    release: &Release,
+    archive: &Asset,
+) -> Result<String, String> {
+    let checksums = release
+        .assets
+        .iter()
+        .find(|asset| asset.name.to_lowercase().contains("sha256"));
+
+    if let Some(checksums) = checksums {
+        let checksums = client
+            .get(&checksums.browser_download_url)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| format!("Failed to fetch checksums: {}", e))?
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read checksums: {}", e))?;
+        return expected_checksum(&checksums, &archive.name)
+            .ok_or_else(|| format!("No checksum published for {}", archive.name));
+    }
+
+    archive
+        .digest
+        .as_deref()
+        .and_then(|digest| digest.strip_prefix("sha256:"))
+        .map(str::to_lowercase)
+        .ok_or_else(|| {
+            format!(
+                "The latest release publishes no SHA-256 checksum for {}, so it cannot be \
+                 verified; download it from {} and choose the binary instead",
+                archive.name, release.html_url
+            )
+        })
+}
+
+fn platform() -> Result<&'static str, String> {
+    match (std::env::consts::ARCH, std::env::consts::OS) {
+        ("x86_64", "linux") => Ok("x86_64-linux"),
+        ("x86_64", "macos") => Ok("x86_64-darwin"),
+        ("aarch64", "macos") => Ok("aarch64-darwin"),
+        (arch, os) => Err(format!(
+            "No hydra-node release is published for {}-{}",
+            arch, os
+        )),
+    }
+}
+
+/// Finds the hash for `file_name` in a `sha256sum`-style listing.
+fn expected_checksum(checksums: &str, file_name: &str) -> Option<String> {
+    checksums.lines().find_map(|line| {
+        let (hash, name) = line.trim().split_once(char::is_whitespace)?;
+        (name.trim().trim_start_matches('*') == file_name).then(|| hash.to_lowercase())
+    })
+}
+
+fn hex_digest(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+fn extract_hydra_node(archive: Vec<u8>) -> Result<Vec<u8>, String> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(archive)).map_err(|e| e.to_string())?;
+
+    for idx in 0..archive.len() {
+        let mut file = archive.by_index(idx).map_err(|e| e.to_string())?;
+
+        if Path::new(file.name()).file_name() == Some("hydra-node".as_ref()) {
+            let mut binary = Vec::new();
+            file.read_to_end(&mut binary).map_err(|e| e.to_string())?;
+            return Ok(binary);
+        }
+    }
+
+    Err("The release archive does not contain hydra-node".to_string())
+}
+
+fn install(binary: &[u8]) -> Result<PathBuf, String> {
+    let home = std::env::var_os("HOME").ok_or("HOME is not set")?;
+    let bin_dir = PathBuf::from(home).join(".local").join("bin");
+    let target = bin_dir.join("hydra-node");
+
+    std::fs::create_dir_all(&bin_dir)
+        .and_then(|_| std::fs::write(&target, binary))
+        .map_err(|e| format!("Failed to write {}: {}", target.display(), e))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        std::fs::set_permissions(&target, std::fs::Permissions::from_mode(0o755))
+            .map_err(|e| format!("Failed to make {} executable: {}", target.display(), e))?;
+    }
+
+    Ok(target)
+}
@@ -0,0 +1,239 @@
+use std::env;
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use super::run_options::{ChainConfig, Host, NetworkId, NodeId, RunOptions};
+
+#[derive(Debug)]
+pub enum FromEnvError {
+    MissingVar(String),
+    InvalidVar { name: String, reason: String },
+}
+
+impl fmt::Display for FromEnvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FromEnvError::MissingVar(name) => write!(f, "{} is not set", name),
+            FromEnvError::InvalidVar { name, reason } => {
+                write!(f, "{} is invalid: {}", name, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FromEnvError {}
+
+impl RunOptions {
+    /// Builds options from `HYDRA_*` and `CARDANO_*` environment variables.
+    /// `HYDRA_NODE_ID` and `HYDRA_SIGNING_KEY` are required; anything else
+    /// that is unset keeps its default. List variables are comma separated.
+    pub fn from_env() -> Result<RunOptions, FromEnvError> {
+        RunOptions::from_vars(|name| env::var(name).ok().filter(|value| !value.is_empty()))
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<RunOptions, FromEnvError> {
+        let required = |name: &str| var(name).ok_or_else(|| FromEnvError::MissingVar(name.into()));
+
+        let mut options = RunOptions {
+            node_id: NodeId(required("HYDRA_NODE_ID")?),
+            hydra_signing_key: required("HYDRA_SIGNING_KEY")?.into(),
+            ..RunOptions::default()
+        };
+
+        if let Some(host) = parse_var(&var, "HYDRA_HOST")? {
+            options.host = host;
+        }
+        if let Some(port) = parse_var(&var, "HYDRA_PORT")? {
+            options.port = port;
+        }
+        if let Some(api_host) = parse_var(&var, "HYDRA_API_HOST")? {
+            options.api_host = api_host;
+        }
+        if let Some(api_port) = parse_var(&var, "HYDRA_API_PORT")? {
+            options.api_port = api_port;
+        }
+        if let Some(monitoring_port) = parse_var(&var, "HYDRA_MONITORING_PORT")? {
+            options.monitoring_port = Some(monitoring_port);
+        }
+        if let Some(peers) = var("HYDRA_PEERS") {
+            options.peers = split_list(&peers)
+                .map(|peer| {
+                    peer.parse::<Host>()
+                        .map_err(|reason| FromEnvError::InvalidVar {
+                            name: "HYDRA_PEERS".to_string(),
+                            reason,
+                        })
+                })
+                .collect::<Result<_, _>>()?;
+        }
+        if let Some(keys) = var("HYDRA_VERIFICATION_KEYS") {
+            options.hydra_verification_keys = split_list(&keys).map(PathBuf::from).collect();
+        }
+        if let Some(dir) = var("HYDRA_PERSISTENCE_DIR") {
+            options.persistence_dir = dir.into();
+        }
+        if let Some(file) = var("HYDRA_PROTOCOL_PARAMETERS") {
+            options
+                .ledger_config
+                .cardano_ledger_protocol_parameters_file = file.into();
+        }
+
+        if let ChainConfig::Direct(config) = &mut options.chain_config {
+            if let Some(network_id) = parse_var::<NetworkId>(&var, "HYDRA_NETWORK")? {
+                config.network_id = network_id;
+            }
+            if let Some(socket) = var("CARDANO_NODE_SOCKET_PATH") {
+                config.node_socket = socket.into();
+            }
+            if let Some(key) = var("CARDANO_SIGNING_KEY") {
+                config.cardano_signing_key = key.into();
+            }
+            if let Some(keys) = var("CARDANO_VERIFICATION_KEYS") {
+                config.cardano_verification_keys = split_list(&keys).map(PathBuf::from).collect();
+            }
+            if let Some(tx_id) = var("HYDRA_SCRIPTS_TX_ID") {
+                config.hydra_scripts_tx_id = tx_id;
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+fn parse_var<T>(
+    var: &impl Fn(&str) -> Option<String>,
+    name: &str,
+) -> Result<Option<T>, FromEnvError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    var(name)
+        .map(|value| {
+            value
+                .trim()
+                .parse()
+                .map_err(|e: T::Err| FromEnvError::InvalidVar {
+                    name: name.to_string(),
+                    reason: e.to_string(),
+                })
+        })
+        .transpose()
+}
+
+fn split_list(list: &str) -> impl Iterator<Item = &str> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn from_vars(vars: &[(&str, &str)]) -> Result<RunOptions, FromEnvError> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        RunOptions::from_vars(|name| vars.get(name).cloned())
+    }
+
+    const REQUIRED: [(&str, &str); 2] = [
+        ("HYDRA_NODE_ID", "alice"),
+        ("HYDRA_SIGNING_KEY", "alice.sk"),
+    ];
+
+    #[test]
+    fn requires_the_node_id_and_signing_key() {
+        assert!(matches!(
+            from_vars(&[("HYDRA_SIGNING_KEY", "alice.sk")]),
+            Err(FromEnvError::MissingVar(name)) if name == "HYDRA_NODE_ID"
+        ));
+        assert!(matches!(
+            from_vars(&[("HYDRA_NODE_ID", "alice")]),
+            Err(FromEnvError::MissingVar(name)) if name == "HYDRA_SIGNING_KEY"
+        ));
+    }
+
+    #[test]
+    fn keeps_defaults_for_unset_variables() {
+        let options = from_vars(&REQUIRED).unwrap();
+
+        assert_eq!(
+            options,
+            RunOptions {
+                node_id: NodeId("alice".to_string()),
+                hydra_signing_key: "alice.sk".into(),
+                ..RunOptions::default()
+            }
+        );
+    }
+
+    #[test]
+    fn overrides_fields_from_variables() {
+        let mut vars = REQUIRED.to_vec();
+        vars.extend([
+            ("HYDRA_HOST", "0.0.0.0"),
+            ("HYDRA_PORT", " 5002 "),
+            ("HYDRA_API_PORT", "4002"),
+            ("HYDRA_MONITORING_PORT", "6002"),
+            ("HYDRA_PEERS", "10.0.0.2:5001, ,10.0.0.3:5001"),
+            ("HYDRA_VERIFICATION_KEYS", "bob.vk,carol.vk"),
+            ("HYDRA_PERSISTENCE_DIR", "state/alice"),
+            ("HYDRA_NETWORK", "testnet:2"),
+            ("CARDANO_NODE_SOCKET_PATH", "/run/cardano/node.socket"),
+            ("HYDRA_SCRIPTS_TX_ID", "abc"),
+        ]);
+        let options = from_vars(&vars).unwrap();
+
+        assert_eq!(options.host.to_string(), "0.0.0.0");
+        assert_eq!(options.port, 5002);
+        assert_eq!(options.api_port, 4002);
+        assert_eq!(options.monitoring_port, Some(6002));
+        assert_eq!(
+            options.peers,
+            vec![
+                "10.0.0.2:5001".parse::<Host>().unwrap(),
+                "10.0.0.3:5001".parse().unwrap()
+            ]
+        );
+        assert_eq!(
+            options.hydra_verification_keys,
+            vec![PathBuf::from("bob.vk"), PathBuf::from("carol.vk")]
+        );
+        assert_eq!(options.persistence_dir, PathBuf::from("state/alice"));
+
+        let ChainConfig::Direct(config) = &options.chain_config else {
+            panic!("expected a Direct chain config");
+        };
+        assert_eq!(config.network_id, NetworkId::Testnet(2));
+        assert_eq!(
+            config.node_socket,
+            PathBuf::from("/run/cardano/node.socket")
+        );
+        assert_eq!(config.hydra_scripts_tx_id, "abc");
+    }
+
+    #[test]
+    fn names_the_invalid_variable() {
+        let mut vars = REQUIRED.to_vec();
+        vars.push(("HYDRA_PORT", "70000"));
+
+        assert!(matches!(
+            from_vars(&vars),
+            Err(FromEnvError::InvalidVar { name, .. }) if name == "HYDRA_PORT"
+        ));
+
+        let mut vars = REQUIRED.to_vec();
+        vars.push(("HYDRA_PEERS", "10.0.0.2"));
+
+        assert!(matches!(
+            from_vars(&vars),
+            Err(FromEnvError::InvalidVar { name, .. }) if name == "HYDRA_PEERS"
+        ));
+    }
+}
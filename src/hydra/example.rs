@@ -0,0 +1,51 @@
+use std::net::SocketAddr;
+
+use super::run_options::{
+    ChainConfig, DirectChainConfig, HydraProtocolParams, LedgerConfig, NetworkId, NodeId,
+    RunOptions, Verbosity,
+};
+use super::schema::CURRENT_SCHEMA_VERSION;
+
+/// A complete two-peer head on the preprod testnet, used to demo a filled in form.
+pub fn example_run_options() -> RunOptions {
+    let peer = |address: &str| address.parse::<SocketAddr>().unwrap().into();
+
+    RunOptions {
+        schema_version: CURRENT_SCHEMA_VERSION,
+        verbosity: Verbosity::Verbose,
+        node_id: NodeId("alice".to_string()),
+        host: "0.0.0.0".parse().unwrap(),
+        port: 5001,
+        peers: vec![peer("10.0.0.2:5001"), peer("10.0.0.3:5001")],
+        api_host: "127.0.0.1".parse().unwrap(),
+        api_port: 4001,
+        tls_cert_path: None,
+        tls_key_path: None,
+        monitoring_port: Some(6001),
+        hydra_signing_key: "credentials/alice.hydra.sk".into(),
+        hydra_verification_keys: vec![
+            "credentials/bob.hydra.vk".into(),
+            "credentials/carol.hydra.vk".into(),
+        ],
+        persistence_dir: "persistence/alice".into(),
+        chain_config: ChainConfig::Direct(DirectChainConfig {
+            network_id: NetworkId::Testnet(1),
+            node_socket: "/run/cardano-node/node.socket".into(),
+            hydra_scripts_tx_id: "03f8deb122fbbd98af8eb58ef56feda37728ec957d39586b78198a0cf624412a"
+                .to_string(),
+            cardano_signing_key: "credentials/alice.cardano.sk".into(),
+            cardano_verification_keys: vec![
+                "credentials/bob.cardano.vk".into(),
+                "credentials/carol.cardano.vk".into(),
+            ],
+            start_chain_from: None,
+            contestation_period: 300,
+            deposit_deadline: 600,
+        }),
+        ledger_config: LedgerConfig {
+            cardano_ledger_protocol_parameters_file: "protocol-parameters.json".into(),
+        },
+        hydra_protocol_params: HydraProtocolParams::default(),
+        extra_args: Vec::new(),
+    }
+}
@@ -1,8 +1,21 @@
+use iced::futures::channel::mpsc;
+use iced::futures::Stream;
 use regex::Regex;
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde::de::{IgnoredAny, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::thread;
 
-#[derive(Serialize, Deserialize, Debug)]
+use super::cardano_address::{detect_address_type, AddressType};
+
+/// How many entries the streaming validation checks between progress reports.
+const PROGRESS_INTERVAL: usize = 1000;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 enum ScriptType {
     SimpleScript,
@@ -11,7 +24,22 @@ enum ScriptType {
     PlutusScriptV3,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+impl ScriptType {
+    /// Maps a `script_language` as written by cardano-cli to its script type.
+    fn from_language(language: &str) -> Option<Self> {
+        match language {
+            "SimpleScriptLanguage"
+            | "SimpleScriptLanguage SimpleScriptV1"
+            | "SimpleScriptLanguage SimpleScriptV2" => Some(ScriptType::SimpleScript),
+            "PlutusScriptLanguage PlutusScriptV1" => Some(ScriptType::PlutusScriptV1),
+            "PlutusScriptLanguage PlutusScriptV2" => Some(ScriptType::PlutusScriptV2),
+            "PlutusScriptLanguage PlutusScriptV3" => Some(ScriptType::PlutusScriptV3),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 struct ScriptDetails {
     cbor_hex: String,
     description: String,
@@ -19,39 +47,162 @@ struct ScriptDetails {
     script_type: ScriptType,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 struct Script {
     script_language: String,
     script: ScriptDetails,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-struct Value {
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Value {
     #[serde(default)]
-    lovelace: u64,
+    pub lovelace: u64,
     #[serde(flatten)]
-    assets: HashMap<String, HashMap<String, i64>>,
+    pub assets: HashMap<String, HashMap<String, i64>>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-struct TxOut {
-    address: String,
-    value: Value,
-    #[serde(skip_serializing_if = "Option::is_none")]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TxOut {
+    pub address: String,
+    pub value: Value,
+    #[serde(
+        default,
+        deserialize_with = "double_option",
+        skip_serializing_if = "Option::is_none"
+    )]
     reference_script: Option<Option<Script>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "double_option",
+        skip_serializing_if = "Option::is_none"
+    )]
     datumhash: Option<Option<String>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "double_option",
+        skip_serializing_if = "Option::is_none"
+    )]
     inline_datum: Option<Option<serde_json::Value>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "double_option",
+        skip_serializing_if = "Option::is_none"
+    )]
     inline_datumhash: Option<Option<String>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "double_option",
+        skip_serializing_if = "Option::is_none"
+    )]
     inline_datum_raw: Option<Option<String>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "double_option",
+        skip_serializing_if = "Option::is_none"
+    )]
     datum: Option<Option<String>>,
 }
 
-type UTxO = HashMap<String, TxOut>;
+pub type UTxO = HashMap<String, TxOut>;
+
+/// Keeps an explicit `null` apart from a missing field: `null` becomes
+/// `Some(None)`, while a missing field falls back to `None` through
+/// `#[serde(default)]`.
+fn double_option<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+impl Value {
+    /// Every native asset as `(policy id, asset name, quantity)`, sorted.
+    pub fn asset_list(&self) -> Vec<(String, String, i64)> {
+        let mut assets: Vec<(String, String, i64)> = self
+            .assets
+            .iter()
+            .flat_map(|(policy_id, names)| {
+                names
+                    .iter()
+                    .map(move |(name, quantity)| (policy_id.clone(), name.clone(), *quantity))
+            })
+            .collect();
+        assets.sort();
+        assets
+    }
+
+    /// Replaces the native assets; later duplicates of a policy id and asset
+    /// name overwrite earlier ones.
+    pub fn set_asset_list(&mut self, assets: Vec<(String, String, i64)>) {
+        self.assets.clear();
+        for (policy_id, name, quantity) in assets {
+            self.assets
+                .entry(policy_id)
+                .or_default()
+                .insert(name, quantity);
+        }
+    }
+}
+
+impl TxOut {
+    /// An output holding only lovelace, without scripts or datums.
+    pub fn new(address: String, lovelace: u64) -> Self {
+        Self {
+            address,
+            value: Value {
+                lovelace,
+                assets: HashMap::new(),
+            },
+            reference_script: None,
+            datumhash: None,
+            inline_datum: None,
+            inline_datumhash: None,
+            inline_datum_raw: None,
+            datum: None,
+        }
+    }
+
+    pub fn inline_datum(&self) -> Option<&serde_json::Value> {
+        self.inline_datum.as_ref()?.as_ref()
+    }
+}
+
+/// A single output of 100 ADA to a preprod address, for trying out an
+/// Offline head.
+pub fn sample_utxo() -> UTxO {
+    let utxo_ref = format!("{}#0", "0".repeat(64));
+    let address = "addr_test1vqg9ywrpx6e50uam03nlu0ewunh3yrscxmjayurmkp52lfskgkq5k".to_string();
+
+    HashMap::from([(utxo_ref, TxOut::new(address, 100_000_000))])
+}
+
+/// Parses UTxO JSON into `(utxo ref, output)` entries sorted by reference.
+pub fn parse_entries(json: &str) -> Result<Vec<(String, TxOut)>, serde_json::Error> {
+    let utxo: UTxO = serde_json::from_str(json)?;
+    let mut entries: Vec<(String, TxOut)> = utxo.into_iter().collect();
+    entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok(entries)
+}
+
+/// Serializes `(utxo ref, output)` entries back into UTxO JSON.
+pub fn entries_to_json(entries: &[(String, TxOut)]) -> Result<String, serde_json::Error> {
+    let utxo: BTreeMap<&String, &TxOut> = entries.iter().map(|(r, out)| (r, out)).collect();
+    serde_json::to_string_pretty(&utxo)
+}
+
+/// Rewrites the UTxO file at `path` with sorted keys and pretty-printed
+/// formatting. Fields are kept exactly as present, including explicit nulls.
+pub fn normalize_file(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let utxo: UTxO = serde_json::from_str(&fs::read_to_string(path)?)?;
+
+    // serde_json's map is ordered, so going through `Value` sorts every key.
+    let mut json = serde_json::to_string_pretty(&serde_json::to_value(&utxo)?)?;
+    json.push('\n');
+
+    fs::write(path, json)?;
+    Ok(())
+}
 
 fn validate_script(script: &Script) -> Result<(), String> {
     if !validate_hex_string(&script.script.cbor_hex) {
@@ -62,6 +213,20 @@ fn validate_script(script: &Script) -> Result<(), String> {
         return Err("Script language cannot be empty".to_string());
     }
 
+    let Some(script_type) = ScriptType::from_language(&script.script_language) else {
+        return Err(format!(
+            "Unknown script language: {}",
+            script.script_language
+        ));
+    };
+
+    if script_type != script.script.script_type {
+        return Err(format!(
+            "Script language {} does not match script type {:?}",
+            script.script_language, script.script.script_type
+        ));
+    }
+
     Ok(())
 }
 
@@ -98,42 +263,349 @@ pub fn validate_json(json: String) -> Result<(), Box<dyn std::error::Error>> {
     let json: UTxO = serde_json::from_str(&json)?;
 
     for (utxo_ref, tx_out) in json.iter() {
-        if !key_regex.is_match(utxo_ref) {
-            return Err(format!("Invalid UTxO ref: {}", utxo_ref).into());
-        }
+        validate_entry(&key_regex, utxo_ref, tx_out)?;
+    }
 
-        if tx_out.address.is_empty() {
-            return Err(format!("Empty address in UTxO: {}", utxo_ref).into());
+    Ok(())
+}
+
+/// Validates UTxO JSON one entry at a time, so only a single output is held
+/// in memory however large the set is. Reports the running entry count to
+/// `progress` and returns the total. Fails with the same messages as
+/// `validate_json`.
+pub fn validate_reader(
+    reader: impl Read,
+    progress: impl FnMut(usize),
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let validator = StreamingValidator {
+        key_regex: Regex::new(r"^[0-9a-f]{64}#[0-9]+$")?,
+        progress,
+    };
+
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+    let result = deserializer.deserialize_map(validator)?;
+    deserializer.end()?;
+
+    Ok(result?)
+}
+
+#[derive(Debug, Clone)]
+pub enum UtxoValidationEvent {
+    Progress(usize),
+    Complete(Result<usize, String>),
+}
+
+/// Runs `validate_reader` over the file at `path` on a background thread.
+pub fn validate_file(path: PathBuf) -> impl Stream<Item = UtxoValidationEvent> {
+    let (sender, receiver) = mpsc::unbounded();
+
+    thread::spawn(move || {
+        let result = File::open(&path)
+            .map_err(|e| format!("Failed to open {}: {}", path.display(), e))
+            .and_then(|file| {
+                validate_reader(BufReader::new(file), |count| {
+                    if count % PROGRESS_INTERVAL == 0 {
+                        let _ = sender.unbounded_send(UtxoValidationEvent::Progress(count));
+                    }
+                })
+                .map_err(|e| e.to_string())
+            });
+        let _ = sender.unbounded_send(UtxoValidationEvent::Complete(result));
+    });
+
+    receiver
+}
+
+struct StreamingValidator<F> {
+    key_regex: Regex,
+    progress: F,
+}
+
+impl<'de, F: FnMut(usize)> Visitor<'de> for StreamingValidator<F> {
+    /// The entry count, or the first validation error. Validation errors are
+    /// kept apart from syntax errors so they read the same as in the batch
+    /// path.
+    type Value = Result<usize, String>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a map of UTxO references to outputs")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(mut self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut count = 0;
+
+        while let Some((utxo_ref, tx_out)) = map.next_entry::<String, TxOut>()? {
+            if let Err(e) = validate_entry(&self.key_regex, &utxo_ref, &tx_out) {
+                // The rest of the map still has to be consumed for the
+                // deserializer to finish cleanly.
+                while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {}
+                return Ok(Err(e.to_string()));
+            }
+
+            count += 1;
+            (self.progress)(count);
         }
 
-        validate_value(&tx_out.value)
-            .map_err(|e| format!("Failed to validate value in UtxO {}: {}", utxo_ref, e))?;
+        Ok(Ok(count))
+    }
+}
+
+fn validate_entry(
+    key_regex: &Regex,
+    utxo_ref: &str,
+    tx_out: &TxOut,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if !key_regex.is_match(utxo_ref) {
+        return Err(format!("Invalid UTxO ref: {}", utxo_ref).into());
+    }
+
+    if tx_out.address.is_empty() {
+        return Err(format!("Empty address in UTxO: {}", utxo_ref).into());
+    }
 
-        if let Some(Some(script)) = &tx_out.reference_script {
-            validate_script(script)
-                .map_err(|e| format!("Failed to validate script in UTxO {}: {}", utxo_ref, e))?;
+    match detect_address_type(&tx_out.address) {
+        AddressType::Unknown => {
+            return Err(format!(
+                "Unrecognized address in UTxO {}: {}",
+                utxo_ref, tx_out.address
+            )
+            .into());
+        }
+        address_type if !address_type.can_hold_funds() => {
+            return Err(format!(
+                "{} address cannot hold funds in UTxO {}: {}",
+                address_type, utxo_ref, tx_out.address
+            )
+            .into());
         }
+        _ => {}
+    }
 
-        if let Some(Some(datumhash)) = &tx_out.datumhash {
-            if !validate_hex_string(datumhash) {
-                return Err(format!("Invalid datumhash format in UTxO: {}", utxo_ref).into());
-            }
+    validate_value(&tx_out.value)
+        .map_err(|e| format!("Failed to validate value in UtxO {}: {}", utxo_ref, e))?;
+
+    if let Some(Some(script)) = &tx_out.reference_script {
+        validate_script(script)
+            .map_err(|e| format!("Failed to validate script in UTxO {}: {}", utxo_ref, e))?;
+    }
+
+    if let Some(Some(datumhash)) = &tx_out.datumhash {
+        if !validate_hex_string(datumhash) {
+            return Err(format!("Invalid datumhash format in UTxO: {}", utxo_ref).into());
         }
+    }
 
-        if let Some(Some(inline_datumhash)) = &tx_out.inline_datumhash {
-            if !validate_hex_string(inline_datumhash) {
-                return Err(
-                    format!("Invalid inline_datumhash format in UTxO: {}", utxo_ref).into(),
-                );
-            }
+    if let Some(Some(inline_datumhash)) = &tx_out.inline_datumhash {
+        if !validate_hex_string(inline_datumhash) {
+            return Err(format!("Invalid inline_datumhash format in UTxO: {}", utxo_ref).into());
         }
+    }
 
-        if let Some(Some(datum)) = &tx_out.datum {
-            if !validate_hex_string(datum) {
-                return Err(format!("Invalid datum format in UTxO: {}", utxo_ref).into());
-            }
+    if let Some(Some(datum)) = &tx_out.datum {
+        if !validate_hex_string(datum) {
+            return Err(format!("Invalid datum format in UTxO: {}", utxo_ref).into());
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_path;
+
+    /// A hand-edited file: unsorted keys, uneven spacing, an explicit null and
+    /// a native asset.
+    const HAND_EDITED: &str = r#"{
+  "1111111111111111111111111111111111111111111111111111111111111111#1": {
+    "value": {"lovelace": 5000000,
+      "0a0b": {"746f6b656e": 7}}, "datumhash": null,
+    "address": "addr_test1vqg9ywrpx6e50uam03nlu0ewunh3yrscxmjayurmkp52lfskgkq5k"},
+  "0000000000000000000000000000000000000000000000000000000000000000#0": {
+    "inline_datum": {"int": 42},
+    "address": "addr_test1vqg9ywrpx6e50uam03nlu0ewunh3yrscxmjayurmkp52lfskgkq5k",
+        "value": {"lovelace": 100000000}
+  }
+}"#;
+
+    #[test]
+    fn normalizing_twice_yields_the_same_bytes() {
+        let path = temp_path("normalize.json");
+        fs::write(&path, HAND_EDITED).unwrap();
+
+        normalize_file(&path).unwrap();
+        let once = fs::read(&path).unwrap();
+        normalize_file(&path).unwrap();
+        let twice = fs::read(&path).unwrap();
+
+        assert_eq!(once, twice);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn normalizing_sorts_keys_and_keeps_explicit_nulls() {
+        let path = temp_path("normalize-nulls.json");
+        fs::write(&path, HAND_EDITED).unwrap();
+
+        normalize_file(&path).unwrap();
+        let json = fs::read_to_string(&path).unwrap();
+
+        let first = json.find(&format!("{}#0", "0".repeat(64))).unwrap();
+        let second = json.find(&format!("{}#1", "1".repeat(64))).unwrap();
+        assert!(first < second);
+        assert!(json.contains(r#""datumhash": null"#));
+        assert!(json.contains(r#""inline_datum": {"#));
+        assert!(!json.contains(r#""datum": null"#));
+        fs::remove_file(&path).unwrap();
+    }
+
+    fn script(language: &str, script_type: ScriptType) -> Script {
+        Script {
+            script_language: language.to_string(),
+            script: ScriptDetails {
+                cbor_hex: "4e4d01000033222220051200120011".to_string(),
+                description: String::new(),
+                script_type,
+            },
+        }
+    }
+
+    #[test]
+    fn recognizes_each_script_language() {
+        let languages = [
+            ("SimpleScriptLanguage", ScriptType::SimpleScript),
+            (
+                "SimpleScriptLanguage SimpleScriptV1",
+                ScriptType::SimpleScript,
+            ),
+            (
+                "SimpleScriptLanguage SimpleScriptV2",
+                ScriptType::SimpleScript,
+            ),
+            (
+                "PlutusScriptLanguage PlutusScriptV1",
+                ScriptType::PlutusScriptV1,
+            ),
+            (
+                "PlutusScriptLanguage PlutusScriptV2",
+                ScriptType::PlutusScriptV2,
+            ),
+            (
+                "PlutusScriptLanguage PlutusScriptV3",
+                ScriptType::PlutusScriptV3,
+            ),
+        ];
+
+        for (language, script_type) in languages {
+            assert_eq!(ScriptType::from_language(language), Some(script_type));
+            assert_eq!(validate_script(&script(language, script_type)), Ok(()));
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_script_languages() {
+        let language = "PlutusScriptLanguage PlutusScriptV4";
+
+        assert_eq!(ScriptType::from_language(language), None);
+        assert_eq!(
+            validate_script(&script(language, ScriptType::PlutusScriptV3)),
+            Err(format!("Unknown script language: {}", language))
+        );
+    }
+
+    #[test]
+    fn rejects_a_language_that_does_not_match_the_script_type() {
+        let language = "PlutusScriptLanguage PlutusScriptV2";
+
+        assert_eq!(
+            validate_script(&script(language, ScriptType::PlutusScriptV3)),
+            Err(format!(
+                "Script language {} does not match script type PlutusScriptV3",
+                language
+            ))
+        );
+    }
+
+    const ADDRESS: &str = "addr_test1vqg9ywrpx6e50uam03nlu0ewunh3yrscxmjayurmkp52lfskgkq5k";
+
+    /// UTxO JSON with `count` valid outputs, and `invalid` as the output at
+    /// index `count / 2` when given.
+    fn generated_utxo(count: usize, invalid: Option<&str>) -> String {
+        let entries: Vec<String> = (0..count)
+            .map(|idx| match invalid {
+                Some(output) if idx == count / 2 => {
+                    format!(r#""{}#{}": {}"#, "a".repeat(64), idx, output)
+                }
+                _ => format!(
+                    r#""{}#{}": {{"address": "{}", "value": {{"lovelace": {}}}}}"#,
+                    "a".repeat(64),
+                    idx,
+                    ADDRESS,
+                    idx + 1
+                ),
+            })
+            .collect();
+
+        format!("{{{}}}", entries.join(",\n"))
+    }
+
+    #[test]
+    fn streams_a_large_file() {
+        let path = temp_path("large-utxo.json");
+        fs::write(&path, generated_utxo(25_000, None)).unwrap();
+
+        let mut reported = Vec::new();
+        let result = validate_reader(BufReader::new(File::open(&path).unwrap()), |count| {
+            reported.push(count)
+        });
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(result.unwrap(), 25_000);
+        assert_eq!(reported.len(), 25_000);
+        assert_eq!(reported.last(), Some(&25_000));
+    }
+
+    #[test]
+    fn streaming_and_batch_validation_fail_alike() {
+        let invalid_outputs = [
+            r#"{"address": "", "value": {"lovelace": 1}}"#.to_string(),
+            r#"{"address": "not an address", "value": {"lovelace": 1}}"#.to_string(),
+            format!(
+                r#"{{"address": "{}", "value": {{"lovelace": 1, "abc": {{"00": 1}}}}}}"#,
+                ADDRESS
+            ),
+            format!(
+                r#"{{"address": "{}", "value": {{"lovelace": 1}}, "datumhash": "xyz"}}"#,
+                ADDRESS
+            ),
+        ];
+
+        for output in &invalid_outputs {
+            let json = generated_utxo(5_000, Some(output));
+
+            let batch = validate_json(json.clone()).unwrap_err().to_string();
+            let streaming = validate_reader(json.as_bytes(), |_| {})
+                .unwrap_err()
+                .to_string();
+
+            assert_eq!(streaming, batch);
+        }
+    }
+
+    #[test]
+    fn streaming_and_batch_validation_reject_bad_refs_alike() {
+        let json = format!(
+            r#"{{"not a ref": {{"address": "{}", "value": {{"lovelace": 1}}}}}}"#,
+            ADDRESS
+        );
+
+        let batch = validate_json(json.clone()).unwrap_err().to_string();
+        let streaming = validate_reader(json.as_bytes(), |_| {})
+            .unwrap_err()
+            .to_string();
+
+        assert_eq!(batch, "Invalid UTxO ref: not a ref");
+        assert_eq!(streaming, batch);
+    }
+}
@@ -0,0 +1,222 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+/// Returns the permission bits of a key file that can be read by its group or
+/// by others. Only owner-only modes such as 0600 and 0400 are considered safe.
+#[cfg(unix)]
+pub fn insecure_permissions(path: &Path) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = fs::metadata(path).ok()?.permissions().mode() & 0o777;
+    (mode & 0o077 != 0).then_some(mode)
+}
+
+#[cfg(not(unix))]
+pub fn insecure_permissions(_path: &Path) -> Option<u32> {
+    None
+}
+
+/// Restricts a key file to be readable and writable by its owner only.
+#[cfg(unix)]
+pub fn restrict_permissions(path: &Path) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::set_permissions(path, fs::Permissions::from_mode(0o600))
+}
+
+#[cfg(not(unix))]
+pub fn restrict_permissions(_path: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+/// A key file in the Cardano text envelope format.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextEnvelope {
+    #[serde(rename = "type")]
+    pub key_type: String,
+    pub description: String,
+    pub cbor_hex: String,
+}
+
+impl TextEnvelope {
+    pub fn load(path: &Path) -> Result<Self, KeyPairError> {
+        let contents =
+            fs::read_to_string(path).map_err(|e| KeyPairError::Io(path.to_path_buf(), e))?;
+        serde_json::from_str(&contents).map_err(|_| KeyPairError::InvalidFormat(path.to_path_buf()))
+    }
+
+    /// The raw key bytes, unwrapped from their CBOR byte string header.
+    fn key_bytes(&self) -> Option<[u8; 32]> {
+        let bytes = hex::decode(&self.cbor_hex).ok()?;
+
+        match bytes.as_slice() {
+            [0x58, 0x20, key @ ..] => key.try_into().ok(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum KeyPairError {
+    Io(PathBuf, io::Error),
+    InvalidFormat(PathBuf),
+    UnsupportedKeyType(String),
+    TypeMismatch {
+        signing: String,
+        verification: String,
+    },
+    Mismatch,
+}
+
+impl fmt::Display for KeyPairError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyPairError::Io(path, e) => write!(f, "Failed to read {}: {}", path.display(), e),
+            KeyPairError::InvalidFormat(path) => {
+                write!(f, "{} is not a Cardano key file", path.display())
+            }
+            KeyPairError::UnsupportedKeyType(key_type) => {
+                write!(f, "Unsupported key type: {}", key_type)
+            }
+            KeyPairError::TypeMismatch {
+                signing,
+                verification,
+            } => write!(
+                f,
+                "Key types do not belong together: {} and {}",
+                signing, verification
+            ),
+            KeyPairError::Mismatch => {
+                write!(f, "The verification key does not belong to the signing key")
+            }
+        }
+    }
+}
+
+impl std::error::Error for KeyPairError {}
+
+/// Checks that the ed25519 verification key at `vk_path` is derived from the
+/// signing key at `sk_path`.
+pub fn validate_key_pair(sk_path: &Path, vk_path: &Path) -> Result<(), KeyPairError> {
+    let signing_key = TextEnvelope::load(sk_path)?;
+    let verification_key = TextEnvelope::load(vk_path)?;
+
+    if !signing_key.key_type.contains("SigningKey") {
+        return Err(KeyPairError::UnsupportedKeyType(signing_key.key_type));
+    }
+    if signing_key
+        .key_type
+        .replace("SigningKey", "VerificationKey")
+        != verification_key.key_type
+    {
+        return Err(KeyPairError::TypeMismatch {
+            signing: signing_key.key_type,
+            verification: verification_key.key_type,
+        });
+    }
+
+    let seed = signing_key
+        .key_bytes()
+        .ok_or_else(|| KeyPairError::UnsupportedKeyType(signing_key.key_type.clone()))?;
+    let expected = verification_key
+        .key_bytes()
+        .ok_or_else(|| KeyPairError::InvalidFormat(vk_path.to_path_buf()))?;
+
+    let derived = ed25519_dalek::SigningKey::from_bytes(&seed)
+        .verifying_key()
+        .to_bytes();
+
+    if derived != expected {
+        return Err(KeyPairError::Mismatch);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_path;
+
+    const SIGNING_KEY_TYPE: &str = "HydraSigningKey_ed25519";
+    const VERIFICATION_KEY_TYPE: &str = "HydraVerificationKey_ed25519";
+
+    /// Writes `key` to a text envelope file of `key_type` and returns its path.
+    fn write_envelope(name: &str, key_type: &str, key: &[u8; 32]) -> PathBuf {
+        let path = temp_path(name);
+        let envelope = serde_json::json!({
+            "type": key_type,
+            "description": "",
+            "cborHex": format!("5820{}", hex::encode(key)),
+        });
+        fs::write(&path, envelope.to_string()).unwrap();
+        path
+    }
+
+    /// Writes the signing key for `seed` and its verification key.
+    fn write_key_pair(name: &str, seed: u8) -> (PathBuf, PathBuf) {
+        let seed = [seed; 32];
+        let verification_key = ed25519_dalek::SigningKey::from_bytes(&seed)
+            .verifying_key()
+            .to_bytes();
+
+        (
+            write_envelope(&format!("{}.sk", name), SIGNING_KEY_TYPE, &seed),
+            write_envelope(
+                &format!("{}.vk", name),
+                VERIFICATION_KEY_TYPE,
+                &verification_key,
+            ),
+        )
+    }
+
+    #[test]
+    fn accepts_a_matching_key_pair() {
+        let (sk, vk) = write_key_pair("matching", 1);
+
+        assert!(validate_key_pair(&sk, &vk).is_ok());
+    }
+
+    #[test]
+    fn rejects_a_verification_key_from_another_pair() {
+        let (sk, _) = write_key_pair("mine", 1);
+        let (_, other_vk) = write_key_pair("theirs", 2);
+
+        assert!(matches!(
+            validate_key_pair(&sk, &other_vk),
+            Err(KeyPairError::Mismatch)
+        ));
+    }
+
+    #[test]
+    fn rejects_keys_of_different_types() {
+        let (sk, _) = write_key_pair("typed", 1);
+        let vk = write_envelope(
+            "payment.vk",
+            "PaymentVerificationKeyShelley_ed25519",
+            &[0; 32],
+        );
+
+        assert!(matches!(
+            validate_key_pair(&sk, &vk),
+            Err(KeyPairError::TypeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_files_that_are_not_key_files() {
+        let (sk, _) = write_key_pair("plain", 1);
+        let vk = temp_path("plain.txt");
+        fs::write(&vk, "not a key").unwrap();
+
+        assert!(matches!(
+            validate_key_pair(&sk, &vk),
+            Err(KeyPairError::InvalidFormat(path)) if path == vk
+        ));
+    }
+}
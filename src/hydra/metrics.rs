@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::time::Duration;
+
+const SCRAPE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Prometheus series shown in the metrics panel, with their labels.
+pub const KEY_METRICS: [(&str, &str); 4] = [
+    ("hydra_head_peers_connected", "Connected peers"),
+    ("hydra_head_events", "Head events"),
+    ("hydra_head_requested_tx", "Requested transactions"),
+    ("hydra_head_confirmed_tx", "Confirmed transactions"),
+];
+
+/// Fetches `/metrics` from the node's monitoring port and returns the
+/// `KEY_METRICS` it reports, in that order.
+pub async fn scrape_metrics(host: IpAddr, port: u16) -> Result<Vec<(&'static str, f64)>, String> {
+    // The monitoring server listens on every interface when the node host is
+    // unspecified; scrape it locally then.
+    let host = if host.is_unspecified() {
+        IpAddr::V4(Ipv4Addr::LOCALHOST)
+    } else {
+        host
+    };
+    let address = SocketAddr::new(host, port);
+    let url = format!("http://{}/metrics", address);
+
+    let client = reqwest::Client::builder()
+        .timeout(SCRAPE_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let body = match client.get(&url).send().await {
+        Ok(response) => response
+            .error_for_status()
+            .map_err(|e| format!("{} answered with an error: {}", url, e))?
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read metrics from {}: {}", url, e))?,
+        Err(e) if e.is_connect() => return Err(format!("Connection to {} was refused", address)),
+        Err(e) => return Err(format!("Failed to reach {}: {}", url, e)),
+    };
+
+    let samples = parse_metrics(&body);
+    Ok(KEY_METRICS
+        .iter()
+        .filter_map(|(name, label)| samples.get(*name).map(|value| (*label, *value)))
+        .collect())
+}
+
+/// Reads the Prometheus text format into metric name → value. Series that
+/// differ only in their labels are summed.
+fn parse_metrics(body: &str) -> HashMap<String, f64> {
+    let mut samples = HashMap::new();
+
+    for line in body.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (series, rest) = match line.find('}') {
+            Some(end) => line.split_at(end + 1),
+            None => line.split_once(char::is_whitespace).unwrap_or((line, "")),
+        };
+        let name = series.split('{').next().unwrap_or(series);
+        let Some(Ok(value)) = rest.split_whitespace().next().map(str::parse::<f64>) else {
+            continue;
+        };
+
+        *samples.entry(name.to_string()).or_insert(0.0) += value;
+    }
+
+    samples
+}
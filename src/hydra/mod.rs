@@ -1,2 +1,23 @@
+pub mod api;
+pub mod binary;
+pub mod bundle;
+pub mod cardano_address;
+pub mod datum;
+pub mod diff;
+pub mod download;
+pub mod env;
+pub mod example;
 pub mod initial_utxo;
+pub mod keys;
+pub mod metrics;
+pub mod node_log;
+pub mod ports;
+pub mod process;
+pub mod protocol_params;
 pub mod run_options;
+pub mod schema;
+pub mod scripts;
+pub mod tls;
+pub mod validation;
+pub mod value;
+pub mod watch;
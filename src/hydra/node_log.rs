@@ -0,0 +1,138 @@
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::thread;
+
+use iced::futures::channel::mpsc;
+use iced::futures::Stream;
+
+/// Size after which the log is rotated when the node is launched.
+const MAX_LOG_SIZE: u64 = 10 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    pub const ALL: [LogLevel; 4] = [
+        LogLevel::Debug,
+        LogLevel::Info,
+        LogLevel::Warn,
+        LogLevel::Error,
+    ];
+
+    fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "debug" => Some(LogLevel::Debug),
+            "info" | "notice" => Some(LogLevel::Info),
+            "warn" | "warning" => Some(LogLevel::Warn),
+            "error" | "critical" | "alert" | "emergency" => Some(LogLevel::Error),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LogLevel::Debug => write!(f, "Debug"),
+            LogLevel::Info => write!(f, "Info"),
+            LogLevel::Warn => write!(f, "Warn"),
+            LogLevel::Error => write!(f, "Error"),
+        }
+    }
+}
+
+/// A line of node output with the level from its JSON `severity` or `level`
+/// field, if it has one.
+#[derive(Debug, Clone)]
+pub struct LogLine {
+    pub level: Option<LogLevel>,
+    pub text: String,
+}
+
+impl LogLine {
+    pub fn parse(text: String) -> Self {
+        let level = serde_json::from_str::<serde_json::Value>(&text)
+            .ok()
+            .and_then(|json| {
+                let level = json.get("severity").or_else(|| json.get("level"))?;
+                LogLevel::parse(level.as_str()?)
+            });
+
+        Self { level, text }
+    }
+
+    /// Lines without a level always pass the level filter.
+    pub fn matches(&self, min_level: LogLevel, filter: &str) -> bool {
+        let level_shown = !matches!(self.level, Some(level) if level < min_level);
+        let filter = filter.to_lowercase();
+
+        level_shown && (filter.is_empty() || self.text.to_lowercase().contains(&filter))
+    }
+}
+
+/// Where node output goes when no log file is configured.
+pub fn default_log_path(persistence_dir: &Path) -> PathBuf {
+    persistence_dir.join("hydra-node.log")
+}
+
+/// Opens `path` for appending, first moving it to `<path>.1` when it has
+/// grown past `MAX_LOG_SIZE`. Only one rotated file is kept.
+pub fn open_log_file(path: &Path) -> io::Result<File> {
+    if fs::metadata(path).is_ok_and(|metadata| metadata.len() > MAX_LOG_SIZE) {
+        let mut rotated = path.as_os_str().to_owned();
+        rotated.push(".1");
+        fs::rename(path, &rotated)?;
+        tracing::info!(path = %path.display(), "rotated node log");
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+/// Streams the lines of both output pipes of the node, appending each one
+/// to `log` as well when given. The pipes are read on their own threads so
+/// a slow disk never holds up the UI.
+pub fn node_output(
+    stdout: impl Read + Send + 'static,
+    stderr: impl Read + Send + 'static,
+    log: Option<File>,
+) -> impl Stream<Item = String> {
+    let (sender, receiver) = mpsc::unbounded();
+    let log = log.map(Arc::new);
+
+    forward_lines(stdout, log.clone(), sender.clone());
+    forward_lines(stderr, log, sender);
+
+    receiver
+}
+
+fn forward_lines(
+    reader: impl Read + Send + 'static,
+    mut log: Option<Arc<File>>,
+    sender: mpsc::UnboundedSender<String>,
+) {
+    thread::spawn(move || {
+        for line in BufReader::new(reader).lines().map_while(Result::ok) {
+            if let Some(file) = &log {
+                if let Err(e) = writeln!(file.as_ref(), "{}", line) {
+                    tracing::warn!(error = %e, "failed to write node log, no longer writing it");
+                    log = None;
+                }
+            }
+            // The receiver is gone once the UI stops listening; keep draining
+            // the pipe so the node never blocks on a full buffer.
+            let _ = sender.unbounded_send(line);
+        }
+    });
+}
@@ -0,0 +1,103 @@
+use std::io::ErrorKind;
+use std::net::{IpAddr, TcpListener};
+
+/// Parses a port typed into the form. Port 0 is rejected: hydra-node would
+/// listen on whatever port the OS picks, which peers cannot know.
+pub fn parse_port(port: &str) -> Result<u16, String> {
+    let port = port.trim();
+
+    if port.is_empty() {
+        return Err("Port is required".to_string());
+    }
+    if !port.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("Port {} is not a number", port));
+    }
+
+    match port.parse::<u16>() {
+        Ok(port) if port != 0 => Ok(port),
+        _ => Err("Port must be 1–65535".to_string()),
+    }
+}
+
+/// Asks the OS for a TCP port that is currently free on `host`. The port is
+/// released again before returning, so another process could still take it.
+pub fn free_port(host: IpAddr) -> Result<u16, String> {
+    TcpListener::bind((host, 0))
+        .and_then(|listener| listener.local_addr())
+        .map(|address| address.port())
+        .map_err(|e| format!("Cannot bind to {} on this machine: {}", host, e))
+}
+
+/// Whether another process is already listening on `port` at `host`.
+pub fn port_in_use(host: IpAddr, port: u16) -> bool {
+    matches!(TcpListener::bind((host, port)), Err(e) if e.kind() == ErrorKind::AddrInUse)
+}
+
+/// Describes every `(host, port)` that something is already listening on.
+pub async fn occupied_ports(addresses: Vec<(IpAddr, u16)>) -> Vec<String> {
+    addresses
+        .into_iter()
+        .filter(|(host, port)| port_in_use(*host, *port))
+        .map(|(host, port)| format!("A process is already listening on {}:{}", host, port))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_the_full_port_range() {
+        assert_eq!(parse_port("1"), Ok(1));
+        assert_eq!(parse_port(" 5001 "), Ok(5001));
+        assert_eq!(parse_port("65535"), Ok(65535));
+    }
+
+    #[test]
+    fn rejects_ports_out_of_range() {
+        let out_of_range = Err("Port must be 1–65535".to_string());
+
+        assert_eq!(parse_port("0"), out_of_range);
+        assert_eq!(parse_port("65536"), out_of_range);
+        assert_eq!(parse_port("70000"), out_of_range);
+    }
+
+    #[test]
+    fn tells_a_missing_port_from_one_that_is_not_a_number() {
+        assert_eq!(parse_port("  "), Err("Port is required".to_string()));
+        assert_eq!(
+            parse_port("abc"),
+            Err("Port abc is not a number".to_string())
+        );
+        assert_eq!(parse_port("-1"), Err("Port -1 is not a number".to_string()));
+    }
+
+    #[test]
+    fn finds_a_free_port_on_localhost() {
+        let host = IpAddr::from([127, 0, 0, 1]);
+
+        let port = free_port(host).unwrap();
+
+        assert_ne!(port, 0);
+        assert!(!port_in_use(host, port));
+    }
+
+    #[test]
+    fn reports_hosts_that_cannot_be_bound() {
+        // 203.0.113.0/24 is reserved for documentation, so no interface has it.
+        let host = IpAddr::from([203, 0, 113, 1]);
+
+        let error = free_port(host).unwrap_err();
+
+        assert!(error.starts_with("Cannot bind to 203.0.113.1"), "{}", error);
+    }
+
+    #[test]
+    fn notices_ports_that_are_taken() {
+        let host = IpAddr::from([127, 0, 0, 1]);
+        let listener = TcpListener::bind((host, 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        assert!(port_in_use(host, port));
+    }
+}
@@ -0,0 +1,20 @@
+use std::io;
+use std::process::Child;
+
+/// Whether `request_shutdown` lets the node exit on its own terms.
+pub const GRACEFUL_SHUTDOWN: bool = cfg!(unix);
+
+/// Asks the node to shut down. On Unix this sends SIGTERM so hydra-node can
+/// flush its persistence; elsewhere the process is killed outright.
+#[cfg(unix)]
+pub fn request_shutdown(child: &mut Child) -> io::Result<()> {
+    use nix::sys::signal::{kill, Signal};
+    use nix::unistd::Pid;
+
+    kill(Pid::from_raw(child.id() as i32), Signal::SIGTERM).map_err(io::Error::from)
+}
+
+#[cfg(not(unix))]
+pub fn request_shutdown(child: &mut Child) -> io::Result<()> {
+    child.kill()
+}
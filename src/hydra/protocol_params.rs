@@ -0,0 +1,91 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    Number,
+    Object,
+}
+
+/// Fields the ledger needs, with the names older `cardano-cli` versions used
+/// listed as alternatives.
+const REQUIRED_FIELDS: &[(&[&str], Kind)] = &[
+    (&["txFeePerByte", "minFeeA"], Kind::Number),
+    (&["txFeeFixed", "minFeeB"], Kind::Number),
+    (&["maxTxSize"], Kind::Number),
+    (&["maxValueSize"], Kind::Number),
+    (&["utxoCostPerByte", "utxoCostPerWord"], Kind::Number),
+    (&["collateralPercentage"], Kind::Number),
+    (&["maxCollateralInputs"], Kind::Number),
+    (&["protocolVersion"], Kind::Object),
+    (&["executionUnitPrices"], Kind::Object),
+    (&["maxTxExecutionUnits"], Kind::Object),
+    (&["costModels"], Kind::Object),
+];
+
+#[derive(Debug)]
+pub enum ProtocolParamsError {
+    Io(PathBuf, io::Error),
+    InvalidJson(PathBuf, serde_json::Error),
+    NotAnObject,
+    MissingField(&'static str),
+    InvalidType {
+        field: &'static str,
+        expected: &'static str,
+    },
+}
+
+impl fmt::Display for ProtocolParamsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProtocolParamsError::Io(path, e) => {
+                write!(f, "Failed to read {}: {}", path.display(), e)
+            }
+            ProtocolParamsError::InvalidJson(path, e) => {
+                write!(f, "{} is not valid JSON: {}", path.display(), e)
+            }
+            ProtocolParamsError::NotAnObject => {
+                write!(f, "Protocol parameters must be a JSON object")
+            }
+            ProtocolParamsError::MissingField(field) => write!(f, "Missing field {}", field),
+            ProtocolParamsError::InvalidType { field, expected } => {
+                write!(f, "Field {} should be {}", field, expected)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ProtocolParamsError {}
+
+/// Checks that the protocol parameters file has the Alonzo/Babbage fields
+/// hydra-node needs, with the expected JSON types.
+pub fn validate_protocol_params(path: &Path) -> Result<(), ProtocolParamsError> {
+    let contents =
+        fs::read_to_string(path).map_err(|e| ProtocolParamsError::Io(path.to_path_buf(), e))?;
+    let params: Value = serde_json::from_str(&contents)
+        .map_err(|e| ProtocolParamsError::InvalidJson(path.to_path_buf(), e))?;
+    let params = params.as_object().ok_or(ProtocolParamsError::NotAnObject)?;
+
+    for (names, kind) in REQUIRED_FIELDS {
+        let Some((field, value)) = names
+            .iter()
+            .find_map(|name| params.get(*name).map(|value| (*name, value)))
+        else {
+            return Err(ProtocolParamsError::MissingField(names[0]));
+        };
+
+        let expected = match kind {
+            Kind::Number if !value.is_number() => "a number",
+            Kind::Object if !value.is_object() => "an object",
+            _ => continue,
+        };
+
+        return Err(ProtocolParamsError::InvalidType { field, expected });
+    }
+
+    Ok(())
+}
@@ -1,14 +1,26 @@
-use std::net::IpAddr;
-use std::path::PathBuf;
+use std::ffi::{OsStr, OsString};
+use std::fmt;
+use std::fs;
+use std::net::{IpAddr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 
+use super::schema::{migrate, CURRENT_SCHEMA_VERSION};
+
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct RunOptions {
+    /// Layout version of the saved file; see `schema::migrate`.
+    #[serde(default)]
+    pub schema_version: u32,
+    #[serde(default)]
     pub verbosity: Verbosity,
     pub node_id: NodeId,
     pub host: IpAddr,
     pub port: u16,
+    #[serde(default)]
     pub peers: Vec<Host>,
     pub api_host: IpAddr,
     pub api_port: u16,
@@ -16,38 +28,111 @@ pub struct RunOptions {
     pub tls_key_path: Option<PathBuf>,
     pub monitoring_port: Option<u16>,
     pub hydra_signing_key: PathBuf,
+    #[serde(default)]
     pub hydra_verification_keys: Vec<PathBuf>,
     pub persistence_dir: PathBuf,
     pub chain_config: ChainConfig,
     pub ledger_config: LedgerConfig,
+    #[serde(default)]
+    pub hydra_protocol_params: HydraProtocolParams,
+    /// Passed to `hydra-node` verbatim after the flags above, for options Juno
+    /// does not model yet.
+    #[serde(default)]
+    pub extra_args: Vec<String>,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
 pub enum Verbosity {
     Quiet,
+    #[default]
     Verbose,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct NodeId(pub String);
 
-#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+impl fmt::Display for NodeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct Host(String);
 
+impl From<SocketAddr> for Host {
+    fn from(address: SocketAddr) -> Self {
+        Host(address.to_string())
+    }
+}
+
+/// Accepts `host:port`, with IPv6 addresses in brackets.
+impl FromStr for Host {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (host, port) = s
+            .rsplit_once(':')
+            .ok_or_else(|| format!("Missing port in {}", s))?;
+
+        if host.is_empty() {
+            return Err(format!("Missing host in {}", s));
+        }
+        if host.contains(':') && !(host.starts_with('[') && host.ends_with(']')) {
+            return Err(format!("IPv6 addresses must be in brackets: {}", s));
+        }
+        port.parse::<u16>()
+            .map_err(|_| format!("Invalid port in {}", s))?;
+
+        Ok(Host(s.to_string()))
+    }
+}
+
+/// Parses a peers file with one `host:port` per line, skipping blank lines
+/// and `#` comments. Returns the peers and the invalid lines, numbered from 1.
+pub fn parse_peer_list(contents: &str) -> (Vec<Host>, Vec<(usize, String)>) {
+    let mut peers = Vec::new();
+    let mut invalid = Vec::new();
+
+    for (idx, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        match line.parse() {
+            Ok(peer) => peers.push(peer),
+            Err(e) => invalid.push((idx + 1, e)),
+        }
+    }
+
+    (peers, invalid)
+}
+
+impl fmt::Display for Host {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub enum ChainConfig {
     Offline(OfflineChainConfig),
     Direct(DirectChainConfig),
 }
 
+/// Fields missing from a saved file take their values from `Default`.
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct OfflineChainConfig {
     pub initial_utxo_file: PathBuf,
 
     pub ledger_genesis_file: Option<PathBuf>,
 }
 
+/// Fields missing from a saved file take their values from `Default`.
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct DirectChainConfig {
     pub network_id: NetworkId,
     pub node_socket: PathBuf,
@@ -60,32 +145,587 @@ pub struct DirectChainConfig {
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct LedgerConfig {
     pub cardano_ledger_protocol_parameters_file: PathBuf,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+/// Head protocol limits, in seconds. Unset values are left to hydra-node.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HydraProtocolParams {
+    pub minimum_contestation_period: Option<u64>,
+    pub maximum_tx_validity_range: Option<u64>,
+}
+
+impl HydraProtocolParams {
+    /// The values used by heads on mainnet.
+    pub fn mainnet() -> Self {
+        Self {
+            minimum_contestation_period: Some(600),
+            maximum_tx_validity_range: Some(7200),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum NetworkId {
     Mainnet,
-    Testnet(u8),
+    Testnet(u32),
 }
 
-impl ToString for NetworkId {
-    fn to_string(&self) -> String {
+/// The network magic of mainnet.
+pub const MAINNET_MAGIC: u32 = 764824073;
+
+/// Shows the network magic, as Cardano tooling does.
+impl fmt::Display for NetworkId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            NetworkId::Mainnet => "0".to_string(),
-            NetworkId::Testnet(magic) => magic.to_string(),
+            NetworkId::Mainnet => write!(f, "{}", MAINNET_MAGIC),
+            NetworkId::Testnet(magic) => write!(f, "{}", magic),
         }
     }
 }
+
+/// Accepts a network magic, `mainnet` or `testnet:<magic>`.
+impl FromStr for NetworkId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        match s.split_once(':') {
+            None if s.eq_ignore_ascii_case("mainnet") => Ok(NetworkId::Mainnet),
+            None if s.parse::<u32>() == Ok(MAINNET_MAGIC) => Ok(NetworkId::Mainnet),
+            None if !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) => {
+                parse_testnet_magic(s)
+            }
+            Some((name, magic)) if name.eq_ignore_ascii_case("testnet") => {
+                parse_testnet_magic(magic)
+            }
+            _ => Err(format!(
+                "Invalid network: {} (expected a network magic, mainnet or testnet:<magic>)",
+                s
+            )),
+        }
+    }
+}
+
+fn parse_testnet_magic(magic: &str) -> Result<NetworkId, String> {
+    magic
+        .parse()
+        .map(NetworkId::Testnet)
+        .map_err(|_| format!("Invalid testnet magic: {}", magic))
+}
+
+/// Well-known Cardano networks offered as shortcuts in the form.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum NetworkPreset {
+    Mainnet,
+    Preview,
+    Preprod,
+    SanchoNet,
+}
+
+impl NetworkPreset {
+    pub const ALL: [NetworkPreset; 4] = [
+        NetworkPreset::Mainnet,
+        NetworkPreset::Preview,
+        NetworkPreset::Preprod,
+        NetworkPreset::SanchoNet,
+    ];
+
+    pub fn network_id(self) -> NetworkId {
+        match self {
+            NetworkPreset::Mainnet => NetworkId::Mainnet,
+            NetworkPreset::Preview => NetworkId::Testnet(2),
+            NetworkPreset::Preprod => NetworkId::Testnet(1),
+            NetworkPreset::SanchoNet => NetworkId::Testnet(4),
+        }
+    }
+}
+
+impl fmt::Display for NetworkPreset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NetworkPreset::Mainnet => write!(f, "Mainnet"),
+            NetworkPreset::Preview => write!(f, "Preview (2)"),
+            NetworkPreset::Preprod => write!(f, "Preprod (1)"),
+            NetworkPreset::SanchoNet => write!(f, "SanchoNet (4)"),
+        }
+    }
+}
+
+/// Form labels and the `hydra-node` flag each one sets.
+pub const FIELD_FLAG_MAP: &[(&str, &str)] = &[
+    ("Quiet Mode", "--quiet"),
+    ("Node ID", "--node-id"),
+    ("Host", "--host"),
+    ("Port", "--port"),
+    ("Peer", "--peer"),
+    ("API Host", "--api-host"),
+    ("API Port", "--api-port"),
+    ("TLS Certificate", "--tls-cert"),
+    ("TLS Key", "--tls-key"),
+    ("Monitoring Port", "--monitoring-port"),
+    ("Signing Key", "--hydra-signing-key"),
+    ("Verification Key", "--hydra-verification-key"),
+    ("Persistence Directory", "--persistence-dir"),
+    ("Initial UTxO File", "--initial-utxo"),
+    ("Ledger Genesis", "--ledger-genesis"),
+    ("Network ID", "--testnet-magic"),
+    ("Node Socket", "--node-socket"),
+    ("Scripts Tx ID", "--hydra-scripts-tx-id"),
+    ("Cardano Signing Key", "--cardano-signing-key"),
+    ("Cardano Key", "--cardano-verification-key"),
+    ("Start Chain From", "--start-chain-from"),
+    ("Contestation Period", "--contestation-period"),
+    ("Deposit Deadline", "--deposit-deadline"),
+    ("Protocol Parameters", "--ledger-protocol-parameters"),
+    ("Min. Contestation Period", "--minimum-contestation-period"),
+    ("Max. Tx Validity Range", "--maximum-tx-validity-range"),
+];
+
+/// The flag set by the form field labelled `label`, with or without its
+/// trailing colon and the number of a list row, as in "Peer 2:".
+pub fn flag_for_label(label: &str) -> Option<&'static str> {
+    let label = label.trim_end_matches(':');
+    let label = match label.rsplit_once(' ') {
+        Some((name, number)) if number.parse::<usize>().is_ok() => name,
+        _ => label,
+    };
+
+    FIELD_FLAG_MAP
+        .iter()
+        .find(|(field, _)| *field == label)
+        .map(|(_, flag)| *flag)
+}
+
+impl RunOptions {
+    /// Builds the argument list passed to `hydra-node` for these options, with
+    /// non-UTF-8 path components replaced. Use `Vec<OsString>::from` when the
+    /// arguments are handed to a process.
+    pub fn to_args(&self) -> Vec<String> {
+        Vec::<OsString>::from(self)
+            .into_iter()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    /// One argument per line, without quoting, for response files and
+    /// `xargs -d '\n'`. Arguments containing newlines cannot be represented.
+    pub fn to_args_lines(&self) -> String {
+        self.to_args().into_iter().map(|arg| arg + "\n").collect()
+    }
+
+    /// The arguments as a JSON string array, e.g. for a docker `command:` or
+    /// a Kubernetes `args:` list. The program name is not included.
+    pub fn to_args_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(&self.to_args())
+    }
+
+    /// Renders the full `hydra-node` invocation as a single shell-quoted line.
+    pub fn to_command_line(&self) -> String {
+        let args: Vec<String> = std::iter::once("hydra-node".to_string())
+            .chain(self.to_args())
+            .collect();
+        join_args(&args)
+    }
+
+    /// Options for another local node next to this one: ports shifted by
+    /// `port_offset`, `suffix` appended to the node id and a persistence
+    /// subfolder named after the new node.
+    pub fn derive_instance(&self, port_offset: u16, suffix: &str) -> Result<RunOptions, String> {
+        let offset = |port: u16| {
+            port.checked_add(port_offset)
+                .ok_or_else(|| format!("Port {} + {} is out of range", port, port_offset))
+        };
+
+        let node_id = format!("{}{}", self.node_id.0, suffix);
+
+        Ok(RunOptions {
+            port: offset(self.port)?,
+            api_port: offset(self.api_port)?,
+            monitoring_port: self.monitoring_port.map(offset).transpose()?,
+            persistence_dir: self.persistence_dir.join(&node_id),
+            node_id: NodeId(node_id),
+            ..self.clone()
+        })
+    }
+
+    /// A copy of these options for the next node of the same kind: the numeric
+    /// suffix of the node id is incremented (or `-2` appended) and every port
+    /// is bumped by one.
+    pub fn next_node(&self) -> RunOptions {
+        let bump = |port: u16| port.checked_add(1).unwrap_or(port);
+
+        RunOptions {
+            node_id: NodeId(increment_suffix(&self.node_id.0)),
+            port: bump(self.port),
+            api_port: bump(self.api_port),
+            monitoring_port: self.monitoring_port.map(bump),
+            ..self.clone()
+        }
+    }
+
+    /// The address other nodes use to reach this node.
+    pub fn listen_address(&self) -> Host {
+        SocketAddr::new(self.host, self.port).into()
+    }
+
+    /// Peers advertised by another node's options: the node itself followed by
+    /// its own peers, excluding this node and peers already configured.
+    pub fn importable_peers(&self, other: &RunOptions) -> Vec<Host> {
+        let own_address = self.listen_address();
+        let mut peers: Vec<Host> = Vec::new();
+
+        for peer in std::iter::once(other.listen_address()).chain(other.peers.iter().cloned()) {
+            if peer != own_address && !self.peers.contains(&peer) && !peers.contains(&peer) {
+                peers.push(peer);
+            }
+        }
+
+        peers
+    }
+
+    /// Reads options saved as JSON with either snake_case or camelCase keys.
+    /// Hand-edited files with comments or trailing commas are accepted
+    /// through a JSON5 fallback.
+    #[tracing::instrument(level = "debug", skip_all, fields(path = %path.display()))]
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let json = fs::read_to_string(path)?;
+
+        let value: serde_json::Value = match serde_json::from_str(&json) {
+            Ok(value) => value,
+            Err(strict) => json5::from_str(&json).map_err(|e| {
+                tracing::debug!(error = %e, "JSON5 fallback failed");
+                strict
+            })?,
+        };
+
+        let value = migrate(rename_keys(value, snake_to_camel))?;
+
+        RunOptions::try_from(value).map_err(|mut e| {
+            // Report fields under the snake_case names used in the file.
+            e.field = e
+                .field
+                .split('.')
+                .map(camel_to_snake)
+                .collect::<Vec<_>>()
+                .join(".");
+            e.into()
+        })
+    }
+
+    /// Saves the options in Juno's snake_case storage format.
+    #[tracing::instrument(level = "debug", skip_all, fields(path = %path.display()))]
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        fs::write(path, self.to_snake_case_json()?)?;
+        Ok(())
+    }
+
+    /// The options as hydra-node names its settings, with camelCase keys.
+    pub fn to_camel_case_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// The options with snake_case keys, as Juno stores them.
+    pub fn to_snake_case_json(&self) -> Result<String, serde_json::Error> {
+        let value = rename_keys(serde_json::to_value(self)?, camel_to_snake);
+        serde_json::to_string_pretty(&value)
+    }
+}
+
+/// A field of the options JSON that does not hold what `RunOptions` expects.
+#[derive(Debug)]
+pub struct RunOptionsError {
+    /// Dotted path of the field, empty for the top level.
+    pub field: String,
+    pub message: String,
+}
+
+impl fmt::Display for RunOptionsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.field.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "Field '{}': {}", self.field, self.message)
+        }
+    }
+}
+
+impl std::error::Error for RunOptionsError {}
+
+/// Deserializes camelCase options JSON, naming the offending field on error.
+impl TryFrom<serde_json::Value> for RunOptions {
+    type Error = RunOptionsError;
+
+    fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {
+        serde_path_to_error::deserialize(value).map_err(|e| {
+            let field = e.path().to_string();
+            RunOptionsError {
+                field: if field == "." { String::new() } else { field },
+                message: e.into_inner().to_string(),
+            }
+        })
+    }
+}
+
+/// Renames every object key in `value`, recursively. Enum variant names such
+/// as `Direct` start with a capital and pass through both renames unchanged.
+fn rename_keys(value: serde_json::Value, rename: fn(&str) -> String) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => map
+            .into_iter()
+            .map(|(key, value)| (rename(&key), rename_keys(value, rename)))
+            .collect(),
+        serde_json::Value::Array(values) => values
+            .into_iter()
+            .map(|value| rename_keys(value, rename))
+            .collect(),
+        value => value,
+    }
+}
+
+fn snake_to_camel(key: &str) -> String {
+    let mut parts = key.split('_');
+    let mut camel = parts.next().unwrap_or_default().to_string();
+
+    for part in parts {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            camel.extend(first.to_uppercase());
+            camel.push_str(chars.as_str());
+        }
+    }
+    camel
+}
+
+fn camel_to_snake(key: &str) -> String {
+    let mut snake = String::with_capacity(key.len());
+
+    for (idx, c) in key.chars().enumerate() {
+        if idx > 0 && c.is_ascii_uppercase() {
+            snake.push('_');
+            snake.push(c.to_ascii_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+/// Chain flags come from the active `ChainConfig` variant only, so Direct-only
+/// flags are never emitted for an Offline head and vice versa.
+impl From<&RunOptions> for Vec<OsString> {
+    fn from(options: &RunOptions) -> Self {
+        let mut args = Vec::new();
+
+        if options.verbosity == Verbosity::Quiet {
+            args.push("--quiet".into());
+        }
+
+        push_flag(&mut args, "--node-id", &options.node_id.0);
+        push_flag(&mut args, "--host", options.host.to_string());
+        push_flag(&mut args, "--port", options.port.to_string());
+
+        for peer in &options.peers {
+            push_flag(&mut args, "--peer", &peer.0);
+        }
+
+        push_flag(&mut args, "--api-host", options.api_host.to_string());
+        push_flag(&mut args, "--api-port", options.api_port.to_string());
+
+        if let Some(cert) = &options.tls_cert_path {
+            push_flag(&mut args, "--tls-cert", cert);
+        }
+        if let Some(key) = &options.tls_key_path {
+            push_flag(&mut args, "--tls-key", key);
+        }
+        if let Some(port) = options.monitoring_port {
+            push_flag(&mut args, "--monitoring-port", port.to_string());
+        }
+
+        push_flag(&mut args, "--hydra-signing-key", &options.hydra_signing_key);
+        for key in &options.hydra_verification_keys {
+            push_flag(&mut args, "--hydra-verification-key", key);
+        }
+
+        push_flag(&mut args, "--persistence-dir", &options.persistence_dir);
+
+        match &options.chain_config {
+            ChainConfig::Offline(config) => {
+                push_flag(&mut args, "--initial-utxo", &config.initial_utxo_file);
+                if let Some(genesis) = &config.ledger_genesis_file {
+                    push_flag(&mut args, "--ledger-genesis", genesis);
+                }
+            }
+            ChainConfig::Direct(config) => {
+                match config.network_id {
+                    NetworkId::Mainnet => args.push("--mainnet".into()),
+                    NetworkId::Testnet(magic) => {
+                        push_flag(&mut args, "--testnet-magic", magic.to_string())
+                    }
+                }
+                push_flag(&mut args, "--node-socket", &config.node_socket);
+                push_flag(
+                    &mut args,
+                    "--hydra-scripts-tx-id",
+                    &config.hydra_scripts_tx_id,
+                );
+                push_flag(
+                    &mut args,
+                    "--cardano-signing-key",
+                    &config.cardano_signing_key,
+                );
+                for key in &config.cardano_verification_keys {
+                    push_flag(&mut args, "--cardano-verification-key", key);
+                }
+                if let Some(point) = &config.start_chain_from {
+                    push_flag(&mut args, "--start-chain-from", point);
+                }
+                push_flag(
+                    &mut args,
+                    "--contestation-period",
+                    config.contestation_period.to_string(),
+                );
+                push_flag(
+                    &mut args,
+                    "--deposit-deadline",
+                    config.deposit_deadline.to_string(),
+                );
+            }
+        }
+
+        push_flag(
+            &mut args,
+            "--ledger-protocol-parameters",
+            &options
+                .ledger_config
+                .cardano_ledger_protocol_parameters_file,
+        );
+
+        let params = &options.hydra_protocol_params;
+        if let Some(period) = params.minimum_contestation_period {
+            push_flag(
+                &mut args,
+                "--minimum-contestation-period",
+                period.to_string(),
+            );
+        }
+        if let Some(range) = params.maximum_tx_validity_range {
+            push_flag(&mut args, "--maximum-tx-validity-range", range.to_string());
+        }
+
+        args.extend(options.extra_args.iter().map(OsString::from));
+
+        args
+    }
+}
+
+fn push_flag(args: &mut Vec<OsString>, flag: &str, value: impl AsRef<OsStr>) {
+    args.push(flag.into());
+    args.push(value.as_ref().to_owned());
+}
+
+/// `node-1` becomes `node-2`, `alice` becomes `alice-2`.
+fn increment_suffix(id: &str) -> String {
+    let stem = id.trim_end_matches(|c: char| c.is_ascii_digit());
+
+    match id[stem.len()..].parse::<u64>() {
+        Ok(n) => format!("{}{}", stem, n + 1),
+        Err(_) => format!("{}-2", id),
+    }
+}
+
+/// Splits a command line fragment on whitespace. Single and double quotes
+/// group words and a backslash escapes the next character outside single
+/// quotes.
+pub fn split_args(input: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current: Option<String> = None;
+    let mut quote: Option<char> = None;
+    let mut chars = input.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"'), '\\') | (None, '\\') => {
+                let escaped = chars.next().ok_or("Trailing backslash")?;
+                current.get_or_insert_with(String::new).push(escaped);
+            }
+            (Some(_), c) => current.get_or_insert_with(String::new).push(c),
+            (None, '\'') | (None, '"') => {
+                quote = Some(c);
+                current.get_or_insert_with(String::new);
+            }
+            (None, c) if c.is_whitespace() => args.extend(current.take()),
+            (None, c) => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+
+    if let Some(q) = quote {
+        return Err(format!("Unterminated {} quote", q));
+    }
+
+    args.extend(current);
+    Ok(args)
+}
+
+/// Joins arguments into a line `split_args` reads back unchanged.
+pub fn join_args(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| shell_quote(arg))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn shell_quote(arg: &str) -> String {
+    let is_safe = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@,+".contains(c));
+
+    if is_safe {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
 pub type TxId = String;
 pub type ChainPoint = String;
 pub type ContestationPeriod = u64;
 pub type DepositDeadline = u64;
 
+impl Default for DirectChainConfig {
+    fn default() -> Self {
+        Self {
+            network_id: NetworkId::Testnet(42),
+            node_socket: "node.socket".into(),
+            hydra_scripts_tx_id: "".into(),
+            cardano_signing_key: "cardano.sk".into(),
+            cardano_verification_keys: Vec::new(),
+            start_chain_from: None,
+            contestation_period: 60,
+            deposit_deadline: 60,
+        }
+    }
+}
+
+impl Default for OfflineChainConfig {
+    fn default() -> Self {
+        Self {
+            initial_utxo_file: "utxo.json".into(),
+            ledger_genesis_file: None,
+        }
+    }
+}
+
 impl Default for RunOptions {
     fn default() -> Self {
         Self {
+            schema_version: CURRENT_SCHEMA_VERSION,
             verbosity: Verbosity::Verbose,
             node_id: NodeId("hydra-node-1".to_string()),
             host: "127.0.0.1".parse().unwrap(),
@@ -99,19 +739,559 @@ impl Default for RunOptions {
             hydra_signing_key: "hydra.sk".into(),
             hydra_verification_keys: Vec::new(),
             persistence_dir: "./".into(),
-            chain_config: ChainConfig::Direct(DirectChainConfig {
-                network_id: NetworkId::Testnet(42),
-                node_socket: "node.socket".into(),
-                hydra_scripts_tx_id: "TxId".to_string(), // TODO: fix
-                cardano_signing_key: "cardano.sk".into(),
-                cardano_verification_keys: Vec::new(),
-                start_chain_from: None,
-                contestation_period: 60,
-                deposit_deadline: 60,
-            }),
+            chain_config: ChainConfig::Direct(DirectChainConfig::default()),
             ledger_config: LedgerConfig {
                 cardano_ledger_protocol_parameters_file: "protocol-parameters.json".into(),
             },
+            hydra_protocol_params: HydraProtocolParams::default(),
+            extra_args: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_file;
+
+    #[test]
+    fn default_clone_equals_default() {
+        assert_eq!(RunOptions::default().clone(), RunOptions::default());
+    }
+
+    #[test]
+    fn offline_and_direct_chain_configs_differ() {
+        assert_ne!(
+            ChainConfig::Offline(OfflineChainConfig::default()),
+            ChainConfig::Direct(DirectChainConfig::default())
+        );
+    }
+
+    #[test]
+    fn mutating_a_clone_leaves_the_original_unchanged() {
+        let original = RunOptions::default();
+        let mut clone = original.clone();
+
+        clone.node_id = NodeId("other".to_string());
+        clone.peers.push("127.0.0.1:5002".parse().unwrap());
+        clone.hydra_verification_keys.push("alice.vk".into());
+        if let ChainConfig::Direct(config) = &mut clone.chain_config {
+            config
+                .cardano_verification_keys
+                .push("alice.cardano.vk".into());
+        }
+
+        assert_eq!(original, RunOptions::default());
+        assert_ne!(clone, original);
+    }
+
+    const DIRECT_ONLY_FLAGS: &[&str] = &[
+        "--mainnet",
+        "--testnet-magic",
+        "--node-socket",
+        "--hydra-scripts-tx-id",
+        "--cardano-signing-key",
+        "--cardano-verification-key",
+        "--start-chain-from",
+        "--contestation-period",
+        "--deposit-deadline",
+    ];
+
+    const OFFLINE_ONLY_FLAGS: &[&str] = &["--initial-utxo", "--ledger-genesis"];
+
+    fn has_flag(args: &[String], flag: &str) -> bool {
+        args.iter().any(|arg| arg == flag)
+    }
+
+    #[test]
+    fn offline_args_leave_out_direct_flags() {
+        let options = RunOptions {
+            chain_config: ChainConfig::Offline(OfflineChainConfig {
+                initial_utxo_file: "utxo.json".into(),
+                ledger_genesis_file: Some("genesis.json".into()),
+            }),
+            ..RunOptions::default()
+        };
+        let args = options.to_args();
+
+        for flag in OFFLINE_ONLY_FLAGS {
+            assert!(has_flag(&args, flag), "{} is missing", flag);
+        }
+        for flag in DIRECT_ONLY_FLAGS {
+            assert!(!has_flag(&args, flag), "{} is emitted", flag);
+        }
+    }
+
+    #[test]
+    fn direct_args_leave_out_offline_flags() {
+        let options = RunOptions {
+            chain_config: ChainConfig::Direct(DirectChainConfig {
+                hydra_scripts_tx_id: "abc".to_string(),
+                cardano_verification_keys: vec!["bob.cardano.vk".into()],
+                start_chain_from: Some("1.abc".to_string()),
+                ..DirectChainConfig::default()
+            }),
+            ..RunOptions::default()
+        };
+        let args = options.to_args();
+
+        for flag in DIRECT_ONLY_FLAGS
+            .iter()
+            .filter(|flag| **flag != "--mainnet")
+        {
+            assert!(has_flag(&args, flag), "{} is missing", flag);
+        }
+        for flag in OFFLINE_ONLY_FLAGS {
+            assert!(!has_flag(&args, flag), "{} is emitted", flag);
+        }
+    }
+
+    #[test]
+    fn mainnet_args_use_the_mainnet_flag() {
+        let options = RunOptions {
+            chain_config: ChainConfig::Direct(DirectChainConfig {
+                network_id: NetworkId::Mainnet,
+                ..DirectChainConfig::default()
+            }),
+            ..RunOptions::default()
+        };
+        let args = options.to_args();
+
+        assert!(has_flag(&args, "--mainnet"));
+        assert!(!has_flag(&args, "--testnet-magic"));
+    }
+
+    #[test]
+    fn os_args_keep_non_ascii_paths() {
+        let options = RunOptions {
+            hydra_signing_key: "clés/алиса.sk".into(),
+            persistence_dir: "état".into(),
+            ..RunOptions::default()
+        };
+        let args = Vec::<OsString>::from(&options);
+
+        assert!(args.contains(&OsString::from("clés/алиса.sk")));
+        assert!(args.contains(&OsString::from("état")));
+        assert!(options.to_args().contains(&"clés/алиса.sk".to_string()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn os_args_keep_non_utf8_paths() {
+        use std::os::unix::ffi::OsStringExt;
+
+        let key = OsString::from_vec(b"keys/\xffalice.sk".to_vec());
+        let options = RunOptions {
+            hydra_signing_key: key.clone().into(),
+            ..RunOptions::default()
+        };
+
+        let args = Vec::<OsString>::from(&options);
+        let flag = args
+            .iter()
+            .position(|arg| arg == "--hydra-signing-key")
+            .unwrap();
+        assert_eq!(args[flag + 1], key);
+
+        // The lossy form replaces the invalid byte instead of failing.
+        assert!(options
+            .to_args()
+            .contains(&"keys/\u{fffd}alice.sk".to_string()));
+    }
+
+    #[test]
+    fn joined_args_split_back_unchanged() {
+        let args: Vec<String> = [
+            "--plain",
+            "with space",
+            "it's",
+            "\"quoted\"",
+            "back\\slash",
+            "",
+            "ünïcode",
+        ]
+        .map(String::from)
+        .to_vec();
+
+        assert_eq!(split_args(&join_args(&args)), Ok(args));
+    }
+
+    #[test]
+    fn command_args_round_trip_through_a_command_line() {
+        let options = RunOptions {
+            persistence_dir: "my state".into(),
+            extra_args: vec!["--flag=it's".to_string()],
+            ..RunOptions::default()
+        };
+        let args = options.to_args();
+
+        assert_eq!(split_args(&join_args(&args)), Ok(args));
+    }
+
+    #[test]
+    fn split_args_rejects_unterminated_input() {
+        assert!(split_args("'open").is_err());
+        assert!(split_args("trailing\\").is_err());
+    }
+
+    #[test]
+    fn increment_suffix_bumps_or_appends_a_number() {
+        assert_eq!(increment_suffix("hydra-node-1"), "hydra-node-2");
+        assert_eq!(increment_suffix("node9"), "node10");
+        assert_eq!(increment_suffix("alice"), "alice-2");
+        assert_eq!(increment_suffix(""), "-2");
+    }
+
+    #[test]
+    fn next_node_bumps_the_node_id_and_ports() {
+        let options = RunOptions {
+            monitoring_port: Some(6001),
+            ..RunOptions::default()
+        };
+        let next = options.next_node();
+
+        assert_eq!(next.node_id, NodeId("hydra-node-2".to_string()));
+        assert_eq!(next.port, options.port + 1);
+        assert_eq!(next.api_port, options.api_port + 1);
+        assert_eq!(next.monitoring_port, Some(6002));
+        assert_eq!(next.persistence_dir, options.persistence_dir);
+    }
+
+    #[test]
+    fn next_node_keeps_ports_at_the_top_of_the_range() {
+        let options = RunOptions {
+            port: u16::MAX,
+            ..RunOptions::default()
+        };
+
+        assert_eq!(options.next_node().port, u16::MAX);
+    }
+
+    const HAND_EDITED_CONFIG: &str = r#"{
+        // Alice's node, edited by hand
+        node_id: "alice",
+        host: "127.0.0.1",
+        port: 5001,
+        api_host: "127.0.0.1",
+        api_port: 4001,
+        hydra_signing_key: "alice.sk",
+        persistence_dir: "state",
+        chain_config: {
+            Offline: { initial_utxo_file: "utxo.json", },
+        },
+        ledger_config: {
+            cardano_ledger_protocol_parameters_file: "protocol-parameters.json",
+        },
+        /* trailing commas everywhere */
+    }"#;
+
+    #[test]
+    fn loads_configs_with_comments_and_trailing_commas() {
+        let path = temp_file("hand-edited.json", HAND_EDITED_CONFIG);
+        let loaded = RunOptions::load(&path);
+        fs::remove_file(&path).unwrap();
+
+        let loaded = loaded.unwrap();
+        assert_eq!(loaded.node_id, NodeId("alice".to_string()));
+        assert_eq!(loaded.persistence_dir, PathBuf::from("state"));
+        assert_eq!(
+            loaded.chain_config,
+            ChainConfig::Offline(OfflineChainConfig {
+                initial_utxo_file: "utxo.json".into(),
+                ledger_genesis_file: None,
+            })
+        );
+        assert_eq!(loaded.tls_cert_path, None);
+    }
+
+    #[test]
+    fn loads_snake_and_camel_case_keys_alike() {
+        let options = RunOptions {
+            monitoring_port: Some(6001),
+            ..RunOptions::default()
+        };
+        let snake = temp_file("snake.json", &options.to_snake_case_json().unwrap());
+        let camel = temp_file("camel.json", &options.to_camel_case_json().unwrap());
+
+        let from_snake = RunOptions::load(&snake);
+        let from_camel = RunOptions::load(&camel);
+        fs::remove_file(&snake).unwrap();
+        fs::remove_file(&camel).unwrap();
+
+        assert_eq!(from_snake.unwrap(), options);
+        assert_eq!(from_camel.unwrap(), options);
+    }
+
+    #[test]
+    fn rejects_files_that_are_not_even_json5() {
+        let path = temp_file("broken.json", "{ node_id: ");
+        let loaded = RunOptions::load(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert!(loaded.is_err());
+    }
+
+    #[test]
+    fn chain_config_defaults_round_trip_through_json() {
+        let direct = DirectChainConfig::default();
+        let json = serde_json::to_string(&direct).unwrap();
+        assert_eq!(
+            serde_json::from_str::<DirectChainConfig>(&json).unwrap(),
+            direct
+        );
+
+        let offline = OfflineChainConfig::default();
+        let json = serde_json::to_string(&offline).unwrap();
+        assert_eq!(
+            serde_json::from_str::<OfflineChainConfig>(&json).unwrap(),
+            offline
+        );
+    }
+
+    #[test]
+    fn missing_chain_config_fields_take_their_defaults() {
+        assert_eq!(
+            serde_json::from_str::<DirectChainConfig>("{}").unwrap(),
+            DirectChainConfig::default()
+        );
+        assert_eq!(
+            serde_json::from_str::<OfflineChainConfig>("{}").unwrap(),
+            OfflineChainConfig::default()
+        );
+    }
+
+    #[test]
+    fn equal_node_ids_hash_alike() {
+        let a = NodeId("a".to_string());
+        let b = NodeId("a".to_string());
+        let state = std::hash::RandomState::new();
+
+        assert_eq!(a, b);
+        assert_eq!(
+            std::hash::BuildHasher::hash_one(&state, &a),
+            std::hash::BuildHasher::hash_one(&state, &b)
+        );
+    }
+
+    #[test]
+    fn node_ids_key_profiles() {
+        let mut profiles = std::collections::HashMap::new();
+        profiles.insert(NodeId("a".to_string()), RunOptions::default());
+
+        assert!(profiles.contains_key(&NodeId("a".to_string())));
+        assert!(!profiles.contains_key(&NodeId("b".to_string())));
+    }
+
+    /// The default options as camelCase JSON with `edit` applied.
+    fn edited_json(edit: impl FnOnce(&mut serde_json::Value)) -> serde_json::Value {
+        let mut value = serde_json::to_value(RunOptions::default()).unwrap();
+        edit(&mut value);
+        value
+    }
+
+    #[test]
+    fn try_from_accepts_valid_options() {
+        let value = edited_json(|_| {});
+
+        assert_eq!(RunOptions::try_from(value).unwrap(), RunOptions::default());
+    }
+
+    #[test]
+    fn try_from_names_a_field_of_the_wrong_type() {
+        let value = edited_json(|value| value["port"] = (-1).into());
+        let error = RunOptions::try_from(value).unwrap_err();
+
+        assert_eq!(error.field, "port");
+        assert!(error.message.contains("expected u16"), "{}", error.message);
+        assert!(error.to_string().starts_with("Field 'port': "));
+    }
+
+    #[test]
+    fn try_from_reports_missing_fields_at_the_top_level() {
+        let value = edited_json(|value| {
+            value.as_object_mut().unwrap().remove("nodeId");
+        });
+        let error = RunOptions::try_from(value).unwrap_err();
+
+        assert_eq!(error.field, "");
+        assert!(error.message.contains("nodeId"), "{}", error.message);
+        assert_eq!(error.to_string(), error.message);
+    }
+
+    #[test]
+    fn try_from_names_nested_fields() {
+        let value = edited_json(|value| {
+            value["chainConfig"]["Direct"]["contestationPeriod"] = "soon".into()
+        });
+        let error = RunOptions::try_from(value).unwrap_err();
+
+        assert_eq!(error.field, "chainConfig.Direct.contestationPeriod");
+    }
+
+    #[test]
+    fn try_from_rejects_unknown_chain_modes() {
+        let value = edited_json(|value| {
+            value["chainConfig"] = serde_json::json!({ "Hybrid": {} });
+        });
+        let error = RunOptions::try_from(value).unwrap_err();
+
+        assert_eq!(error.field, "chainConfig");
+        assert!(
+            error.message.contains("unknown variant"),
+            "{}",
+            error.message
+        );
+    }
+
+    #[test]
+    fn try_from_rejects_non_objects() {
+        let error = RunOptions::try_from(serde_json::json!(5)).unwrap_err();
+
+        assert_eq!(error.field, "");
+    }
+
+    #[test]
+    fn default_args_as_a_json_array() {
+        let json = RunOptions::default().to_args_json().unwrap();
+
+        assert_eq!(
+            json,
+            concat!(
+                r#"["--node-id","hydra-node-1","--host","127.0.0.1","--port","5001","#,
+                r#""--api-host","127.0.0.1","--api-port","4001","#,
+                r#""--hydra-signing-key","hydra.sk","--persistence-dir","./","#,
+                r#""--testnet-magic","42","--node-socket","node.socket","#,
+                r#""--hydra-scripts-tx-id","","--cardano-signing-key","cardano.sk","#,
+                r#""--contestation-period","60","--deposit-deadline","60","#,
+                r#""--ledger-protocol-parameters","protocol-parameters.json"]"#,
+            )
+        );
+        assert_eq!(
+            serde_json::from_str::<Vec<String>>(&json).unwrap(),
+            RunOptions::default().to_args()
+        );
+    }
+
+    #[test]
+    fn args_lines_keep_spaces_unquoted_on_one_line() {
+        let options = RunOptions {
+            persistence_dir: "my state".into(),
+            ..RunOptions::default()
+        };
+
+        let lines = options.to_args_lines();
+
+        assert!(lines.ends_with('\n'));
+        assert_eq!(lines.lines().collect::<Vec<_>>(), options.to_args());
+        let flag = lines
+            .lines()
+            .position(|line| line == "--persistence-dir")
+            .unwrap();
+        assert_eq!(lines.lines().nth(flag + 1), Some("my state"));
+    }
+
+    const PEERS_FILE: &str = "\
+# Peers of the alice node
+127.0.0.1:5002
+
+  [::1]:5003
+# bob is offline for now
+# 127.0.0.1:5004
+carol.example.com:5005
+127.0.0.1
+";
+
+    #[test]
+    fn parses_a_peers_file() {
+        let path = temp_file("peers.txt", PEERS_FILE);
+        let contents = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let (peers, invalid) = parse_peer_list(&contents);
+
+        assert_eq!(
+            peers,
+            vec![
+                Host("127.0.0.1:5002".to_string()),
+                Host("[::1]:5003".to_string()),
+                Host("carol.example.com:5005".to_string())
+            ]
+        );
+        assert_eq!(invalid, vec![(8, "Missing port in 127.0.0.1".to_string())]);
+    }
+
+    #[test]
+    fn extra_args_come_last() {
+        let extra_args = vec!["--peer-connection-timeout".to_string(), "30".to_string()];
+        let options = RunOptions {
+            extra_args: extra_args.clone(),
+            ..RunOptions::default()
+        };
+
+        let args = options.to_args();
+
+        assert_eq!(args[args.len() - 2..], extra_args);
+        assert_eq!(args[..args.len() - 2], RunOptions::default().to_args());
+    }
+
+    #[test]
+    fn flags_for_labels_ignore_colons_and_row_numbers() {
+        assert_eq!(flag_for_label("Port:"), Some("--port"));
+        assert_eq!(flag_for_label("Quiet Mode"), Some("--quiet"));
+        assert_eq!(flag_for_label("Peer 2:"), Some("--peer"));
+        assert_eq!(
+            flag_for_label("Cardano Key 10:"),
+            Some("--cardano-verification-key")
+        );
+        assert_eq!(flag_for_label("Profile:"), None);
+    }
+
+    #[test]
+    fn network_presets_set_their_network_ids() {
+        assert_eq!(NetworkPreset::Preview.network_id(), NetworkId::Testnet(2));
+        assert_eq!(NetworkPreset::Preprod.network_id(), NetworkId::Testnet(1));
+        assert_eq!(NetworkPreset::SanchoNet.network_id(), NetworkId::Testnet(4));
+        assert_eq!(NetworkPreset::Mainnet.network_id(), NetworkId::Mainnet);
+    }
+
+    #[test]
+    fn parses_network_ids() {
+        let parse = |s: &str| s.parse::<NetworkId>();
+
+        assert_eq!(parse("mainnet"), Ok(NetworkId::Mainnet));
+        assert_eq!(parse("764824073"), Ok(NetworkId::Mainnet));
+        assert_eq!(parse("42"), Ok(NetworkId::Testnet(42)));
+        assert_eq!(parse("testnet:2"), Ok(NetworkId::Testnet(2)));
+        assert_eq!(parse(" 1097911063 "), Ok(NetworkId::Testnet(1097911063)));
+        assert_eq!(
+            parse("testnet:1097911063"),
+            Ok(NetworkId::Testnet(1097911063))
+        );
+        assert_eq!(parse("4294967295"), Ok(NetworkId::Testnet(u32::MAX)));
+    }
+
+    #[test]
+    fn rejects_invalid_network_ids() {
+        for network in ["", "preview", "testnet:", "testnet:abc", "4294967296", "-1"] {
+            assert!(network.parse::<NetworkId>().is_err(), "{} parsed", network);
         }
     }
+
+    #[test]
+    fn large_testnet_magics_reach_the_arguments() {
+        let options = RunOptions {
+            chain_config: ChainConfig::Direct(DirectChainConfig {
+                network_id: NetworkId::Testnet(1097911063),
+                ..DirectChainConfig::default()
+            }),
+            ..RunOptions::default()
+        };
+        let args = options.to_args();
+        let flag = args
+            .iter()
+            .position(|arg| arg == "--testnet-magic")
+            .unwrap();
+
+        assert_eq!(args[flag + 1], "1097911063");
+    }
 }
@@ -0,0 +1,89 @@
+use serde_json::Value;
+
+/// Layout version written into every saved configuration.
+pub const CURRENT_SCHEMA_VERSION: u32 = 1;
+
+/// Upgrades camelCase options JSON from an older layout to the current one,
+/// one version at a time. Files from before versioning count as version 0.
+pub fn migrate(mut value: Value) -> Result<Value, String> {
+    let mut version = value
+        .get("schemaVersion")
+        .map(|version| {
+            version
+                .as_u64()
+                .and_then(|version| u32::try_from(version).ok())
+                .ok_or_else(|| format!("Invalid schema version: {}", version))
+        })
+        .transpose()?
+        .unwrap_or(0);
+
+    if version > CURRENT_SCHEMA_VERSION {
+        return Err(format!(
+            "Schema version {} is newer than this version of Juno supports ({})",
+            version, CURRENT_SCHEMA_VERSION
+        ));
+    }
+
+    while version < CURRENT_SCHEMA_VERSION {
+        value = match version {
+            // Version 1 only introduced the version field itself.
+            0 => value,
+            _ => unreachable!("no migration from schema version {}", version),
+        };
+        version += 1;
+        tracing::debug!(version, "migrated options");
+    }
+
+    if let Value::Object(map) = &mut value {
+        map.insert("schemaVersion".to_string(), CURRENT_SCHEMA_VERSION.into());
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::hydra::run_options::RunOptions;
+
+    /// Default options as saved before the schema version existed.
+    fn v0_fixture() -> Value {
+        let mut value = serde_json::to_value(RunOptions::default()).unwrap();
+        value.as_object_mut().unwrap().remove("schemaVersion");
+        value
+    }
+
+    #[test]
+    fn upgrades_unversioned_files() {
+        let migrated = migrate(v0_fixture()).unwrap();
+
+        assert_eq!(migrated["schemaVersion"], json!(CURRENT_SCHEMA_VERSION));
+        assert_eq!(
+            RunOptions::try_from(migrated).unwrap(),
+            RunOptions::default()
+        );
+    }
+
+    #[test]
+    fn keeps_current_files_as_they_are() {
+        let current = serde_json::to_value(RunOptions::default()).unwrap();
+
+        assert_eq!(migrate(current.clone()), Ok(current));
+    }
+
+    #[test]
+    fn rejects_newer_files() {
+        let newer = json!({ "schemaVersion": CURRENT_SCHEMA_VERSION + 1 });
+
+        assert!(migrate(newer).unwrap_err().contains("is newer than"));
+    }
+
+    #[test]
+    fn rejects_invalid_versions() {
+        for version in [json!("one"), json!(-1), json!(1.5), json!(u64::MAX)] {
+            let error = migrate(json!({ "schemaVersion": version })).unwrap_err();
+            assert!(error.starts_with("Invalid schema version"), "{}", error);
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use super::binary::Version;
+use super::run_options::{NetworkId, TxId};
+
+/// Scripts transactions published by the Hydra team, as
+/// `(network, hydra-node major.minor, tx id)`. New releases are listed in
+/// `networks.json` of the cardano-scaling/hydra repository.
+const KNOWN_SCRIPTS_TX_IDS: &[(NetworkId, (u64, u64), &str)] = &[(
+    NetworkId::Testnet(1),
+    (0, 20),
+    "03f8deb122fbbd98af8eb58ef56feda37728ec957d39586b78198a0cf624412a",
+)];
+
+/// The published scripts tx id for `network`, for the given hydra-node
+/// version or else the newest one known.
+pub fn known_scripts_tx_id(network: &NetworkId, version: Option<Version>) -> Option<TxId> {
+    let mut candidates = KNOWN_SCRIPTS_TX_IDS
+        .iter()
+        .filter(|(known_network, _, _)| known_network == network);
+
+    match version {
+        Some((major, minor, _)) => candidates
+            .find(|(_, known_version, _)| *known_version == (major, minor))
+            .map(|(_, _, tx_id)| tx_id.to_string()),
+        None => candidates
+            .max_by_key(|(_, known_version, _)| *known_version)
+            .map(|(_, _, tx_id)| tx_id.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_every_known_tx_id_by_network_and_version() {
+        for (network, (major, minor), tx_id) in KNOWN_SCRIPTS_TX_IDS {
+            assert_eq!(
+                known_scripts_tx_id(network, Some((*major, *minor, 0))).as_deref(),
+                Some(*tx_id),
+                "{} on {}.{}",
+                network,
+                major,
+                minor
+            );
+        }
+    }
+
+    #[test]
+    fn falls_back_to_the_newest_tx_id_without_a_version() {
+        let newest = KNOWN_SCRIPTS_TX_IDS
+            .iter()
+            .filter(|(network, _, _)| *network == NetworkId::Testnet(1))
+            .max_by_key(|(_, version, _)| *version)
+            .map(|(_, _, tx_id)| tx_id.to_string());
+
+        assert_eq!(known_scripts_tx_id(&NetworkId::Testnet(1), None), newest);
+    }
+
+    #[test]
+    fn has_no_tx_id_for_unpublished_versions_or_networks() {
+        assert_eq!(
+            known_scripts_tx_id(&NetworkId::Testnet(1), Some((0, 1, 0))),
+            None
+        );
+        assert_eq!(known_scripts_tx_id(&NetworkId::Testnet(42), None), None);
+    }
+}
@@ -0,0 +1,40 @@
+use std::fs;
+use std::io::BufReader;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The validity period of a TLS certificate.
+#[derive(Debug, Clone)]
+pub struct CertValidity {
+    pub not_before: String,
+    pub not_after: String,
+    not_after_timestamp: i64,
+}
+
+impl CertValidity {
+    /// Reads the first certificate in a PEM file. Returns `None` when the file
+    /// cannot be read or parsed.
+    pub fn load(path: &Path) -> Option<Self> {
+        let file = fs::File::open(path).ok()?;
+        let der = rustls_pemfile::certs(&mut BufReader::new(file))
+            .next()?
+            .ok()?;
+        let (_, cert) = x509_parser::parse_x509_certificate(&der).ok()?;
+        let validity = cert.validity();
+
+        Some(Self {
+            not_before: validity.not_before.to_string(),
+            not_after: validity.not_after.to_string(),
+            not_after_timestamp: validity.not_after.timestamp(),
+        })
+    }
+
+    /// Seconds until the certificate expires, negative once it has.
+    pub fn seconds_remaining(&self) -> i64 {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs() as i64);
+
+        self.not_after_timestamp - now
+    }
+}
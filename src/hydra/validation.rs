@@ -0,0 +1,680 @@
+use std::ffi::OsString;
+use std::fmt;
+use std::fs;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use super::run_options::{ChainConfig, RunOptions};
+use super::tls::CertValidity;
+
+/// Slots beyond this are decades ahead of every public network, so a point
+/// past it was most likely copied wrongly.
+const MAX_PLAUSIBLE_SLOT: u64 = 1_000_000_000;
+
+/// Length of a block header hash in hex.
+const BLOCK_HASH_LENGTH: usize = 64;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ValidationIssue {
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl ValidationIssue {
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            message: message.into(),
+        }
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.severity {
+            Severity::Warning => write!(f, "warning: {}", self.message),
+            Severity::Error => write!(f, "error: {}", self.message),
+        }
+    }
+}
+
+impl RunOptions {
+    /// Checks the options for likely misconfigurations. Warnings do not
+    /// prevent the node from starting, errors will.
+    #[tracing::instrument(level = "debug", skip_all, fields(node_id = %self.node_id.0))]
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+
+        check_peer_key_counts(self, &mut issues);
+        check_duplicate_keys(
+            "Hydra verification keys",
+            &self.hydra_verification_keys,
+            &mut issues,
+        );
+        check_signing_key_not_verification_key(
+            "Hydra",
+            &self.hydra_signing_key,
+            &self.hydra_verification_keys,
+            &mut issues,
+        );
+        if let ChainConfig::Direct(config) = &self.chain_config {
+            if config.hydra_scripts_tx_id.trim().is_empty() {
+                issues.push(ValidationIssue::error(
+                    "No Hydra scripts transaction ID is set; hydra-node needs one to follow \
+                     the chain",
+                ));
+            }
+            check_duplicate_keys(
+                "Cardano verification keys",
+                &config.cardano_verification_keys,
+                &mut issues,
+            );
+            check_signing_key_not_verification_key(
+                "Cardano",
+                &config.cardano_signing_key,
+                &config.cardano_verification_keys,
+                &mut issues,
+            );
+            if let Some(point) = &config.start_chain_from {
+                issues.extend(check_chain_point(point));
+            }
+        }
+        check_tls_files(self, &mut issues);
+        check_extra_args_not_duplicated(self, &mut issues);
+
+        tracing::debug!(issues = issues.len(), "validated run options");
+        issues
+    }
+
+    /// Like `validate`, but also checks the options against the other saved
+    /// profiles.
+    pub fn validate_with_profiles<'a>(
+        &self,
+        profiles: impl IntoIterator<Item = &'a RunOptions>,
+    ) -> Vec<ValidationIssue> {
+        let mut issues = self.validate();
+        check_shared_persistence_dir(self, profiles, &mut issues);
+        issues
+    }
+}
+
+/// Two nodes writing to the same persistence directory corrupt each other's
+/// state.
+fn check_shared_persistence_dir<'a>(
+    options: &RunOptions,
+    profiles: impl IntoIterator<Item = &'a RunOptions>,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    let persistence_dir = normalize_path(&options.persistence_dir);
+
+    for profile in profiles {
+        if normalize_path(&profile.persistence_dir) == persistence_dir {
+            issues.push(ValidationIssue::warning(format!(
+                "Profile {} also uses persistence directory {}",
+                profile.node_id,
+                persistence_dir.display()
+            )));
+        }
+    }
+}
+
+/// Every peer needs its Hydra verification key, and in a Direct head its
+/// Cardano verification key too, so the key lists should be as long as the
+/// peer list.
+fn check_peer_key_counts(options: &RunOptions, issues: &mut Vec<ValidationIssue>) {
+    let peers = options.peers.len();
+    let mut key_counts = vec![("Hydra", options.hydra_verification_keys.len())];
+
+    if let ChainConfig::Direct(config) = &options.chain_config {
+        key_counts.push(("Cardano", config.cardano_verification_keys.len()));
+    }
+
+    for (label, keys) in key_counts {
+        if !key_count_mismatch(peers, keys) {
+            continue;
+        }
+
+        let message = match (peers, keys) {
+            (_, 0) => format!(
+                "{} peer(s) configured but no {} verification keys are set",
+                peers, label
+            ),
+            (0, _) => format!(
+                "{} {} verification key(s) set but no peers are configured",
+                keys, label
+            ),
+            _ => format!(
+                "{} peer(s) configured but {} {} verification key(s) are set",
+                peers, keys, label
+            ),
+        };
+        issues.push(ValidationIssue::warning(message));
+    }
+}
+
+/// Whether the number of verification keys does not match the number of
+/// other participants.
+fn key_count_mismatch(peers: usize, keys: usize) -> bool {
+    peers != keys
+}
+
+fn check_duplicate_keys(label: &str, keys: &[PathBuf], issues: &mut Vec<ValidationIssue>) {
+    let normalized: Vec<Option<PathBuf>> = keys
+        .iter()
+        .map(|key| (!key.as_os_str().is_empty()).then(|| normalize_path(key)))
+        .collect();
+
+    for (i, first) in normalized.iter().enumerate() {
+        let Some(first) = first else { continue };
+
+        for (j, second) in normalized.iter().enumerate().skip(i + 1) {
+            if second.as_ref() == Some(first) {
+                issues.push(ValidationIssue::warning(format!(
+                    "{} {} and {} both point to {}",
+                    label,
+                    i + 1,
+                    j + 1,
+                    first.display()
+                )));
+            }
+        }
+    }
+}
+
+fn check_signing_key_not_verification_key(
+    label: &str,
+    signing_key: &Path,
+    verification_keys: &[PathBuf],
+    issues: &mut Vec<ValidationIssue>,
+) {
+    if signing_key.as_os_str().is_empty() {
+        return;
+    }
+
+    let signing_key = normalize_path(signing_key);
+
+    for (idx, key) in verification_keys.iter().enumerate() {
+        if normalize_path(key) == signing_key {
+            issues.push(ValidationIssue::warning(format!(
+                "{} signing key is also listed as verification key {} ({}); \
+                 check that the signing key field points at a .sk file",
+                label,
+                idx + 1,
+                signing_key.display()
+            )));
+        }
+    }
+}
+
+fn check_tls_files(options: &RunOptions, issues: &mut Vec<ValidationIssue>) {
+    match (&options.tls_cert_path, &options.tls_key_path) {
+        (None, None) => {}
+        (Some(_), None) | (None, Some(_)) => issues.push(ValidationIssue::warning(
+            "TLS needs both a certificate and a key; only one is set",
+        )),
+        (Some(cert), Some(key)) => {
+            if let Err(e) = check_pem_certificates(cert) {
+                issues.push(ValidationIssue::warning(format!(
+                    "TLS certificate {}: {}",
+                    cert.display(),
+                    e
+                )));
+            } else if let Some(validity) = CertValidity::load(cert) {
+                check_certificate_expiry(cert, &validity, issues);
+            }
+            if let Err(e) = check_pem_private_key(key) {
+                issues.push(ValidationIssue::warning(format!(
+                    "TLS key {}: {}",
+                    key.display(),
+                    e
+                )));
+            }
+        }
+    }
+}
+
+fn check_certificate_expiry(
+    cert: &Path,
+    validity: &CertValidity,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    const EXPIRY_WARNING_SECONDS: i64 = 30 * 24 * 60 * 60;
+
+    let remaining = validity.seconds_remaining();
+    if remaining <= 0 {
+        issues.push(ValidationIssue::warning(format!(
+            "TLS certificate {} expired on {}",
+            cert.display(),
+            validity.not_after
+        )));
+    } else if remaining < EXPIRY_WARNING_SECONDS {
+        issues.push(ValidationIssue::warning(format!(
+            "TLS certificate {} expires on {}",
+            cert.display(),
+            validity.not_after
+        )));
+    }
+}
+
+fn check_pem_certificates(path: &Path) -> Result<(), String> {
+    let file = fs::File::open(path).map_err(|e| e.to_string())?;
+    let certs = rustls_pemfile::certs(&mut BufReader::new(file))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("not a valid PEM file ({})", e))?;
+
+    if certs.is_empty() {
+        return Err("contains no PEM certificate".to_string());
+    }
+    Ok(())
+}
+
+fn check_pem_private_key(path: &Path) -> Result<(), String> {
+    let file = fs::File::open(path).map_err(|e| e.to_string())?;
+
+    match rustls_pemfile::private_key(&mut BufReader::new(file)) {
+        Ok(Some(_)) => Ok(()),
+        Ok(None) => Err("contains no PEM private key".to_string()),
+        Err(e) => Err(format!("not a valid PEM file ({})", e)),
+    }
+}
+
+fn check_extra_args_not_duplicated(options: &RunOptions, issues: &mut Vec<ValidationIssue>) {
+    if options.extra_args.is_empty() {
+        return;
+    }
+
+    let modeled = RunOptions {
+        extra_args: Vec::new(),
+        ..options.clone()
+    };
+    let emitted: Vec<OsString> = Vec::from(&modeled);
+
+    for arg in &options.extra_args {
+        let flag = arg.split_once('=').map_or(arg.as_str(), |(flag, _)| flag);
+
+        if flag.starts_with("--") && emitted.iter().any(|emitted| emitted == flag) {
+            issues.push(ValidationIssue::warning(format!(
+                "Extra argument {} duplicates a flag Juno already passes to hydra-node",
+                flag
+            )));
+        }
+    }
+}
+
+/// Checks a `--start-chain-from` point, which must be `<slot>.<block hash>`.
+pub fn check_chain_point(point: &str) -> Option<ValidationIssue> {
+    let Some((slot, hash)) = point.split_once('.') else {
+        return Some(ValidationIssue::error(format!(
+            "Start chain from {} is not of the form <slot>.<block hash>",
+            point
+        )));
+    };
+
+    let Ok(slot) = slot.parse::<u64>() else {
+        return Some(ValidationIssue::error(format!(
+            "Start chain from slot {} is not a number",
+            slot
+        )));
+    };
+
+    if hash.len() != BLOCK_HASH_LENGTH || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Some(ValidationIssue::error(format!(
+            "Start chain from block hash must be {} hex characters, got {:?}",
+            BLOCK_HASH_LENGTH, hash
+        )));
+    }
+
+    (slot > MAX_PLAUSIBLE_SLOT).then(|| {
+        ValidationIssue::warning(format!(
+            "Start chain from slot {} is implausibly large; check it belongs to this network",
+            slot
+        ))
+    })
+}
+
+/// Describes why `path` cannot be a running cardano-node's socket, if it
+/// cannot.
+#[cfg(unix)]
+pub fn node_socket_problem(path: &Path) -> Option<String> {
+    use std::os::unix::fs::FileTypeExt;
+
+    match fs::metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() => None,
+        Ok(_) => Some(format!("{} is not a socket", path.display())),
+        Err(_) => Some(format!("{} does not exist", path.display())),
+    }
+}
+
+#[cfg(not(unix))]
+pub fn node_socket_problem(path: &Path) -> Option<String> {
+    (!path.exists()).then(|| format!("{} does not exist", path.display()))
+}
+
+/// Resolves a path to a comparable form, falling back to a lexical cleanup
+/// when the file does not exist yet.
+fn normalize_path(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.components().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hydra::run_options::{NodeId, OfflineChainConfig, RunOptions};
+    use crate::test_util::temp_path;
+
+    /// Default options with `peers` peers and `keys` Hydra and Cardano
+    /// verification keys.
+    fn head(peers: usize, keys: usize) -> RunOptions {
+        let mut options = RunOptions::default();
+        options.peers = (0..peers)
+            .map(|idx| format!("127.0.0.1:{}", 5002 + idx).parse().unwrap())
+            .collect();
+        options.hydra_verification_keys = (0..keys)
+            .map(|idx| PathBuf::from(format!("peer-{}.vk", idx)))
+            .collect();
+        if let ChainConfig::Direct(config) = &mut options.chain_config {
+            config.cardano_verification_keys = (0..keys)
+                .map(|idx| PathBuf::from(format!("peer-{}.cardano.vk", idx)))
+                .collect();
+        }
+        options
+    }
+
+    fn warnings(options: &RunOptions, needle: &str) -> Vec<String> {
+        options
+            .validate()
+            .into_iter()
+            .filter(|issue| issue.severity == Severity::Warning && issue.message.contains(needle))
+            .map(|issue| issue.message)
+            .collect()
+    }
+
+    #[test]
+    fn warns_about_peers_without_verification_keys() {
+        let options = head(2, 0);
+
+        assert_eq!(
+            warnings(&options, "no Hydra verification keys"),
+            ["2 peer(s) configured but no Hydra verification keys are set"]
+        );
+        assert_eq!(warnings(&options, "no Cardano verification keys").len(), 1);
+    }
+
+    #[test]
+    fn accepts_peers_with_verification_keys() {
+        assert!(warnings(&head(2, 2), "verification key").is_empty());
+    }
+
+    #[test]
+    fn accepts_a_single_party_head_without_keys() {
+        assert!(warnings(&head(0, 0), "verification key").is_empty());
+
+        let offline = RunOptions {
+            chain_config: ChainConfig::Offline(OfflineChainConfig::default()),
+            ..head(0, 0)
+        };
+        assert!(warnings(&offline, "verification key").is_empty());
+    }
+
+    #[test]
+    fn warns_about_keys_without_peers() {
+        assert_eq!(
+            warnings(&head(0, 1), "Hydra verification key"),
+            ["1 Hydra verification key(s) set but no peers are configured"]
+        );
+    }
+
+    #[test]
+    fn flags_duplicate_verification_keys_after_normalizing() {
+        let mut options = head(3, 0);
+        options.hydra_verification_keys = vec![
+            "keys/alice.vk".into(),
+            "keys/bob.vk".into(),
+            "keys//alice.vk".into(),
+        ];
+        if let ChainConfig::Direct(config) = &mut options.chain_config {
+            config.cardano_verification_keys = vec![
+                "keys/alice.cardano.vk".into(),
+                "keys/alice.cardano.vk/".into(),
+                "keys/bob.cardano.vk".into(),
+            ];
+        }
+
+        assert_eq!(
+            warnings(&options, "both point to"),
+            [
+                "Hydra verification keys 1 and 3 both point to keys/alice.vk",
+                "Cardano verification keys 1 and 2 both point to keys/alice.cardano.vk",
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_distinct_and_empty_verification_keys() {
+        let mut options = head(3, 0);
+        options.hydra_verification_keys = vec!["".into(), "keys/alice.vk".into(), "".into()];
+
+        assert!(warnings(&options, "both point to").is_empty());
+    }
+
+    #[test]
+    fn key_count_mismatch_compares_keys_to_peers() {
+        assert!(!key_count_mismatch(0, 0));
+        assert!(!key_count_mismatch(2, 2));
+        assert!(key_count_mismatch(2, 1));
+        assert!(key_count_mismatch(1, 2));
+        assert!(key_count_mismatch(0, 1));
+    }
+
+    #[test]
+    fn warns_about_mismatched_key_counts() {
+        let options = head(3, 2);
+
+        assert_eq!(
+            warnings(&options, "peer(s) configured but"),
+            [
+                "3 peer(s) configured but 2 Hydra verification key(s) are set",
+                "3 peer(s) configured but 2 Cardano verification key(s) are set",
+            ]
+        );
+    }
+
+    #[test]
+    fn only_checks_cardano_key_counts_for_direct_heads() {
+        let offline = RunOptions {
+            chain_config: ChainConfig::Offline(OfflineChainConfig::default()),
+            ..head(1, 1)
+        };
+
+        assert!(warnings(&offline, "Cardano").is_empty());
+    }
+
+    #[test]
+    fn node_socket_must_exist() {
+        let path = temp_path("missing.socket");
+
+        assert_eq!(
+            node_socket_problem(&path),
+            Some(format!("{} does not exist", path.display()))
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn node_socket_must_be_a_socket() {
+        let path = temp_path("regular.socket");
+        fs::write(&path, "").unwrap();
+
+        let problem = node_socket_problem(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(problem, Some(format!("{} is not a socket", path.display())));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn accepts_a_listening_socket() {
+        let path = temp_path("node.socket");
+        let listener = std::os::unix::net::UnixListener::bind(&path).unwrap();
+
+        let problem = node_socket_problem(&path);
+        drop(listener);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(problem, None);
+    }
+
+    const BLOCK_HASH: &str = "7d2d5b2ed0c2b4a3e0b6d8d6c1a1f0e5c0b2d9a8f7e6d5c4b3a2918f7e6d5c4b";
+
+    #[test]
+    fn accepts_a_chain_point() {
+        assert_eq!(check_chain_point(&format!("49533501.{}", BLOCK_HASH)), None);
+    }
+
+    #[test]
+    fn rejects_malformed_chain_points() {
+        let error =
+            |point: &str| check_chain_point(point).map(|issue| (issue.severity, issue.message));
+
+        assert_eq!(
+            error("49533501"),
+            Some((
+                Severity::Error,
+                "Start chain from 49533501 is not of the form <slot>.<block hash>".to_string()
+            ))
+        );
+        assert_eq!(
+            error(&format!("slot.{}", BLOCK_HASH)),
+            Some((
+                Severity::Error,
+                "Start chain from slot slot is not a number".to_string()
+            ))
+        );
+        assert_eq!(
+            error("49533501.abc"),
+            Some((
+                Severity::Error,
+                "Start chain from block hash must be 64 hex characters, got \"abc\"".to_string()
+            ))
+        );
+        assert!(error(&format!("49533501.{}", BLOCK_HASH.replace('a', "g"))).is_some());
+    }
+
+    #[test]
+    fn warns_about_implausible_slots() {
+        let issue = check_chain_point(&format!("{}.{}", MAX_PLAUSIBLE_SLOT + 1, BLOCK_HASH));
+
+        assert_eq!(issue.map(|issue| issue.severity), Some(Severity::Warning));
+    }
+
+    #[test]
+    fn validate_checks_the_chain_point_of_direct_heads() {
+        let mut options = head(0, 0);
+        if let ChainConfig::Direct(config) = &mut options.chain_config {
+            config.start_chain_from = Some("49533501".to_string());
+        }
+
+        assert!(options
+            .validate()
+            .iter()
+            .any(|issue| issue.message.starts_with("Start chain from")));
+    }
+
+    #[test]
+    fn direct_heads_need_a_scripts_tx_id() {
+        let missing = |options: &RunOptions| {
+            options
+                .validate()
+                .iter()
+                .any(|issue| issue.message.starts_with("No Hydra scripts transaction ID"))
+        };
+        let mut options = head(0, 0);
+        assert!(missing(&options));
+
+        if let ChainConfig::Direct(config) = &mut options.chain_config {
+            config.hydra_scripts_tx_id = "03f8deb122fbbd98af8eb58ef56feda3".to_string();
+        }
+        assert!(!missing(&options));
+    }
+
+    #[test]
+    fn warns_about_profiles_sharing_a_persistence_dir() {
+        let options = RunOptions {
+            persistence_dir: "state/alice".into(),
+            ..head(0, 0)
+        };
+        let bob = RunOptions {
+            node_id: NodeId("bob".to_string()),
+            persistence_dir: "state/alice/".into(),
+            ..head(0, 0)
+        };
+        let carol = RunOptions {
+            node_id: NodeId("carol".to_string()),
+            persistence_dir: "state/carol".into(),
+            ..head(0, 0)
+        };
+
+        let shared: Vec<String> = options
+            .validate_with_profiles([&bob, &carol])
+            .into_iter()
+            .filter(|issue| issue.severity == Severity::Warning)
+            .map(|issue| issue.message)
+            .filter(|message| message.contains("persistence directory"))
+            .collect();
+
+        assert_eq!(
+            shared,
+            ["Profile bob also uses persistence directory state/alice"]
+        );
+    }
+
+    #[test]
+    fn warns_about_extra_args_that_duplicate_modeled_flags() {
+        let options = RunOptions {
+            extra_args: vec![
+                "--port=5002".to_string(),
+                "--node-id".to_string(),
+                "bob".to_string(),
+            ],
+            ..RunOptions::default()
+        };
+
+        assert_eq!(
+            warnings(&options, "Extra argument"),
+            [
+                "Extra argument --port duplicates a flag Juno already passes to hydra-node",
+                "Extra argument --node-id duplicates a flag Juno already passes to hydra-node"
+            ]
+        );
+    }
+
+    #[test]
+    fn accepts_extra_args_juno_does_not_pass() {
+        let options = RunOptions {
+            extra_args: vec![
+                "--peer-connection-timeout=30".to_string(),
+                "--port".to_string(),
+            ],
+            ..RunOptions::default()
+        };
+
+        assert_eq!(
+            warnings(&options, "Extra argument"),
+            ["Extra argument --port duplicates a flag Juno already passes to hydra-node"]
+        );
+        assert!(warnings(&RunOptions::default(), "Extra argument").is_empty());
+    }
+}
@@ -0,0 +1,55 @@
+pub const LOVELACE_PER_ADA: u64 = 1_000_000;
+
+pub fn lovelace_to_ada(lovelace: u64) -> f64 {
+    lovelace as f64 / LOVELACE_PER_ADA as f64
+}
+
+/// Rounds to the nearest lovelace; negative amounts become zero.
+pub fn ada_to_lovelace(ada: f64) -> u64 {
+    (ada * LOVELACE_PER_ADA as f64).round() as u64
+}
+
+/// Formats an amount as "X.XXXXXX ₳" without going through floating point.
+pub fn format_ada(lovelace: u64) -> String {
+    format!(
+        "{}.{:06} ₳",
+        lovelace / LOVELACE_PER_ADA,
+        lovelace % LOVELACE_PER_ADA
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The 45 billion ADA that will ever exist, in lovelace.
+    const MAX_SUPPLY: u64 = 45_000_000_000 * LOVELACE_PER_ADA;
+
+    #[test]
+    fn converts_one_lovelace() {
+        assert_eq!(lovelace_to_ada(1), 0.000001);
+        assert_eq!(ada_to_lovelace(0.000001), 1);
+        assert_eq!(format_ada(1), "0.000001 ₳");
+    }
+
+    #[test]
+    fn converts_one_ada() {
+        assert_eq!(lovelace_to_ada(LOVELACE_PER_ADA), 1.0);
+        assert_eq!(ada_to_lovelace(1.0), LOVELACE_PER_ADA);
+        assert_eq!(format_ada(LOVELACE_PER_ADA), "1.000000 ₳");
+    }
+
+    #[test]
+    fn converts_the_max_supply() {
+        assert_eq!(lovelace_to_ada(MAX_SUPPLY), 45_000_000_000.0);
+        assert_eq!(ada_to_lovelace(45_000_000_000.0), MAX_SUPPLY);
+        assert_eq!(format_ada(MAX_SUPPLY), "45000000000.000000 ₳");
+    }
+
+    #[test]
+    fn rounds_to_the_nearest_lovelace() {
+        assert_eq!(ada_to_lovelace(1.0000004), 1_000_000);
+        assert_eq!(ada_to_lovelace(1.0000006), 1_000_001);
+        assert_eq!(ada_to_lovelace(-1.0), 0);
+    }
+}
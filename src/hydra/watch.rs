@@ -0,0 +1,58 @@
+use std::path::{Path, PathBuf};
+
+use iced::futures::channel::mpsc;
+use iced::futures::{SinkExt, Stream, StreamExt};
+use notify::{RecursiveMode, Watcher};
+
+/// Emits an item every time `path` is written, created or replaced on disk.
+/// The parent directory is watched rather than the file itself, since many
+/// editors save by renaming a temporary file over the original.
+pub fn watch_file(path: PathBuf) -> impl Stream<Item = Result<(), String>> {
+    iced::stream::channel(16, move |mut output| async move {
+        let (sender, mut events) = mpsc::unbounded();
+
+        let watcher = notify::recommended_watcher(move |event| {
+            let _ = sender.unbounded_send(event);
+        });
+        let mut watcher = match watcher {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                let _ = output.send(Err(e.to_string())).await;
+                return;
+            }
+        };
+
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        if let Err(e) = watcher.watch(dir, RecursiveMode::NonRecursive) {
+            let _ = output
+                .send(Err(format!("Failed to watch {}: {}", dir.display(), e)))
+                .await;
+            return;
+        }
+
+        while let Some(event) = events.next().await {
+            let event: notify::Result<notify::Event> = event;
+
+            let result = match event {
+                Ok(event) if event.kind.is_access() => continue,
+                Ok(event)
+                    if event
+                        .paths
+                        .iter()
+                        .any(|p| p.file_name() == path.file_name()) =>
+                {
+                    Ok(())
+                }
+                Ok(_) => continue,
+                Err(e) => Err(e.to_string()),
+            };
+
+            if output.send(result).await.is_err() {
+                break;
+            }
+        }
+    })
+}
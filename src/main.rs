@@ -1,8 +1,47 @@
-use views::configure::HydraSettings;
+use app::AppState;
+use clap::Parser;
+use cli::Cli;
+use iced::{window, Size};
+use session::SessionState;
+use tracing_subscriber::EnvFilter;
 
+mod app;
+mod app_config;
+mod cli;
 mod hydra;
+mod profiles;
+mod session;
+#[cfg(test)]
+mod test_util;
 mod views;
 
 fn main() -> iced::Result {
-    iced::run("Juno", HydraSettings::update, HydraSettings::view)
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("juno=info")),
+        )
+        .with_writer(std::io::stderr)
+        .init();
+
+    let cli = Cli::parse();
+    let config = cli.gui_config();
+    if let Some(result) = cli.run_headless() {
+        if let Err(e) = result {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    let session = SessionState::load();
+
+    iced::application("Juno", AppState::update, AppState::view)
+        .subscription(AppState::subscription)
+        .window(window::Settings {
+            size: Size::new(session.window_width, session.window_height),
+            // The session is saved before the window is closed.
+            exit_on_close_request: false,
+            ..window::Settings::default()
+        })
+        .run_with(move || AppState::new(config))
 }
@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::hydra::run_options::{NodeId, RunOptions};
+
+/// The saved profiles other than the one being edited, kept across launches so
+/// they are still checked against the current options.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileStore {
+    pub profiles: Vec<RunOptions>,
+}
+
+impl ProfileStore {
+    /// `~/.config/juno/profiles.json`, or `None` when there is no home directory.
+    pub fn path() -> Option<PathBuf> {
+        let home = std::env::var_os("HOME")?;
+        Some(
+            PathBuf::from(home)
+                .join(".config")
+                .join("juno")
+                .join("profiles.json"),
+        )
+    }
+
+    /// Reads the saved profiles by node ID, falling back to none when there
+    /// are none yet or they cannot be read.
+    pub fn load() -> HashMap<NodeId, RunOptions> {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str::<Self>(&json).ok())
+            .unwrap_or_default()
+            .profiles
+            .into_iter()
+            .map(|profile| (profile.node_id.clone(), profile))
+            .collect()
+    }
+
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub fn save(profiles: &HashMap<NodeId, RunOptions>) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Self::path().ok_or("HOME is not set")?;
+
+        let mut profiles: Vec<RunOptions> = profiles.values().cloned().collect();
+        profiles.sort_by(|a, b| a.node_id.0.cmp(&b.node_id.0));
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(&Self { profiles })?)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// Where the previous session left off, restored on the next launch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionState {
+    pub last_config_path: Option<PathBuf>,
+    pub window_width: f32,
+    pub window_height: f32,
+    /// Vertical offset of the settings form, in pixels.
+    pub scroll_offset: f32,
+    pub active_section: String,
+}
+
+impl Default for SessionState {
+    fn default() -> Self {
+        Self {
+            last_config_path: None,
+            window_width: 1024.0,
+            window_height: 768.0,
+            scroll_offset: 0.0,
+            active_section: String::new(),
+        }
+    }
+}
+
+impl SessionState {
+    /// `~/.config/juno/session.json`, or `None` when there is no home directory.
+    pub fn path() -> Option<PathBuf> {
+        let home = std::env::var_os("HOME")?;
+        Some(
+            PathBuf::from(home)
+                .join(".config")
+                .join("juno")
+                .join("session.json"),
+        )
+    }
+
+    /// Reads the previous session, falling back to defaults when there is none
+    /// or it cannot be read.
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Self::path().ok_or("HOME is not set")?;
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_json() {
+        let session = SessionState {
+            last_config_path: Some("/home/alice/hydra/config.json".into()),
+            window_width: 1280.0,
+            window_height: 720.5,
+            scroll_offset: 312.25,
+            active_section: "advanced".to_string(),
+        };
+
+        let json = serde_json::to_string_pretty(&session).unwrap();
+
+        assert_eq!(
+            serde_json::from_str::<SessionState>(&json).unwrap(),
+            session
+        );
+    }
+
+    #[test]
+    fn defaults_missing_fields() {
+        let session: SessionState = serde_json::from_str(r#"{ "window_width": 800.0 }"#).unwrap();
+
+        assert_eq!(
+            session,
+            SessionState {
+                window_width: 800.0,
+                ..SessionState::default()
+            }
+        );
+    }
+}
@@ -0,0 +1,22 @@
+use std::fs;
+use std::path::PathBuf;
+
+/// A path in the temp directory unique to this test process.
+pub fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("juno-{}-{}", std::process::id(), name))
+}
+
+/// Writes `contents` to `temp_path(name)` and returns that path.
+pub fn temp_file(name: &str, contents: &str) -> PathBuf {
+    let path = temp_path(name);
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+/// An empty directory at `temp_path(name)`.
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = temp_path(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
@@ -0,0 +1,72 @@
+use iced::widget::{button, column, container, scrollable, text};
+use iced::{Element, Length};
+
+use crate::views::configure::Message;
+
+pub const CHANGELOG: &str = include_str!("../../CHANGELOG.md");
+
+/// The changelog sections for releases newer than `last_seen`, or all of
+/// them when no version was seen before.
+pub fn changes_since(last_seen: Option<&str>) -> String {
+    let mut changes = Vec::new();
+
+    for section in CHANGELOG.split("\n## ").skip(1) {
+        let version = section.lines().next().unwrap_or_default().trim();
+        if Some(version) == last_seen {
+            break;
+        }
+        changes.push(format!("## {}", section.trim_end()));
+    }
+
+    changes.join("\n\n")
+}
+
+/// The changes to show on startup, or `None` when `last_seen` is already
+/// this version of Juno.
+pub fn pending_changes(last_seen: Option<&str>) -> Option<String> {
+    (last_seen != Some(env!("CARGO_PKG_VERSION"))).then(|| changes_since(last_seen))
+}
+
+pub fn view(changes: &str) -> Element<Message> {
+    container(
+        column![
+            text(format!("What's new in Juno {}", env!("CARGO_PKG_VERSION"))).size(24),
+            scrollable(text(changes)).height(Length::Fixed(200.0)),
+            button("Dismiss").on_press(Message::DismissChangelog)
+        ]
+        .spacing(10),
+    )
+    .padding(10)
+    .width(Length::Fill)
+    .style(container::rounded_box)
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shows_the_panel_after_an_update() {
+        let changes = pending_changes(Some("0.0.1")).unwrap();
+
+        assert!(changes.contains(&format!("## {}", env!("CARGO_PKG_VERSION"))));
+    }
+
+    #[test]
+    fn shows_the_panel_on_first_run() {
+        assert_eq!(pending_changes(None), Some(changes_since(None)));
+    }
+
+    #[test]
+    fn hides_the_panel_once_the_version_was_seen() {
+        assert_eq!(pending_changes(Some(env!("CARGO_PKG_VERSION"))), None);
+    }
+
+    #[test]
+    fn stops_at_the_last_seen_version() {
+        let changes = changes_since(Some(env!("CARGO_PKG_VERSION")));
+
+        assert!(!changes.contains(&format!("## {}", env!("CARGO_PKG_VERSION"))));
+    }
+}
@@ -1,16 +1,70 @@
 use iced::widget::{
-    button, checkbox, column, container, horizontal_rule, pick_list, row, scrollable, text,
-    text_input, Column, Container,
+    button, checkbox, column, container, horizontal_rule, mouse_area, opaque, pick_list,
+    progress_bar, row, scrollable, stack, text, text_editor, text_input, tooltip, Column,
+    Container, Space,
 };
-use iced::{alignment, Alignment, Element, Length, Task};
-use std::path::PathBuf;
+use iced::{
+    alignment, event, font, keyboard, time, window, Alignment, Element, Event, Font, Length, Size,
+    Subscription, Task,
+};
+use std::collections::{HashMap, VecDeque};
+use std::ffi::OsString;
+use std::mem;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
 
+use crate::app_config::{AppConfig, DialogPurpose};
+use crate::hydra::api::{probe_api, send_abort};
+use crate::hydra::binary::{
+    find_on_path, hydra_node_help, hydra_node_version, parse_version, unsupported_flags, Version,
+    HYDRA_NODE_BINARY, INSTALL_INSTRUCTIONS,
+};
+use crate::hydra::bundle::{export_bundle, import_bundle};
+use crate::hydra::cardano_address::detect_address_type;
+use crate::hydra::datum::{format_plutus_data, highlight};
+use crate::hydra::diff::RunOptionsDiff;
+use crate::hydra::download::{download_hydra_node, DownloadEvent};
+use crate::hydra::example::example_run_options;
+use crate::hydra::initial_utxo::{
+    entries_to_json, normalize_file, parse_entries, sample_utxo, validate_file, validate_json,
+    TxOut, UtxoValidationEvent,
+};
+use crate::hydra::keys::{insecure_permissions, restrict_permissions, validate_key_pair};
+use crate::hydra::metrics::scrape_metrics;
+use crate::hydra::node_log::{default_log_path, node_output, open_log_file, LogLevel, LogLine};
+use crate::hydra::ports::{free_port, occupied_ports, parse_port};
+use crate::hydra::process::{request_shutdown, GRACEFUL_SHUTDOWN};
+use crate::hydra::protocol_params::validate_protocol_params;
 use crate::hydra::run_options::{
-    ChainConfig, DirectChainConfig, NetworkId, NodeId, OfflineChainConfig, RunOptions, Verbosity,
+    flag_for_label, join_args, parse_peer_list, split_args, ChainConfig, DirectChainConfig, Host,
+    HydraProtocolParams, NetworkPreset, NodeId, OfflineChainConfig, RunOptions, TxId, Verbosity,
 };
+use crate::hydra::scripts::known_scripts_tx_id;
+use crate::hydra::tls::CertValidity;
+use crate::hydra::validation::{check_chain_point, node_socket_problem, Severity, ValidationIssue};
+use crate::hydra::value::{ada_to_lovelace, format_ada, lovelace_to_ada};
+use crate::hydra::watch::watch_file;
+use crate::profiles::ProfileStore;
+use crate::session::SessionState;
+use crate::views::changelog;
+use crate::views::datum_modal::{self, DatumPreview};
+use crate::views::diff_modal::{self, DiffModal, DiffSource};
+use crate::views::history::History;
+use crate::views::key_preview::{self, KeyPreview, KeyPreviewError};
+use crate::views::welcome::Template;
 
 #[derive(Debug, Clone)]
 pub enum Message {
+    AdvancedToggled(bool),
+    LockSettings,
+    Undo,
+    Redo,
+    UnlockSettings,
+    HideSensitiveToggled(bool),
+
     VerbosityToggled(bool),
     NodeIdChanged(String),
     HostChanged(String),
@@ -18,6 +72,17 @@ pub enum Message {
 
     ApiHostChanged(String),
     ApiPortChanged(String),
+    FindFreePort(IpAddr, fn(String) -> Message),
+    FreePortFailed(String),
+    TestApiConnection,
+    AbortHead,
+    ConfirmAbortHead,
+    CancelAbortHead,
+    AbortHeadResult(Result<String, String>),
+    ApiConnectionResult(Result<String, String>),
+    MetricsToggled,
+    RefreshMetrics(Instant),
+    MetricsScraped(Result<Vec<(&'static str, f64)>, String>),
     MonitoringPortChanged(String),
 
     TlsCertPathChanged(String),
@@ -28,20 +93,341 @@ pub enum Message {
     HydraSigningKeyChanged(String),
     AddHydraVerificationKey,
     RemoveHydraVerificationKey(usize),
+    MoveHydraVerificationKeyUp(usize),
+    MoveHydraVerificationKeyDown(usize),
     HydraVerificationKeyChanged(usize, String),
     BrowseHydraSigningKey,
     BrowseHydraVerificationKey(usize),
+    FixKeyPermissions(PathBuf),
+    PreviewKey(PathBuf),
+    ValidateKeyPair,
+    CloseKeyPreview,
 
     ChainConfigTypeChanged(ChainConfigType),
     NetworkIdChanged(String),
+    ApplyNetworkPreset(NetworkPreset),
     NodeSocketChanged(String),
+    NodeSocketStatus(Result<(), String>),
+    HydraScriptsTxIdChanged(String),
+    CardanoSigningKeyChanged(String),
+    AddCardanoVerificationKey,
+    RemoveCardanoVerificationKey(usize),
+    MoveCardanoVerificationKeyUp(usize),
+    MoveCardanoVerificationKeyDown(usize),
+    CardanoVerificationKeyChanged(usize, String),
+    StartChainFromChanged(String),
+    ContestationPeriodChanged(String),
+    DepositDeadlineChanged(String),
+    LedgerGenesisChanged(String),
+    ProtocolParametersChanged(String),
+    HydraParamsToggled,
+    MinimumContestationPeriodChanged(String),
+    MaximumTxValidityRangeChanged(String),
+    ResetHydraParamsToMainnet,
 
     InitialUtxoChanged(String),
+    UtxoTextChanged(text_editor::Action),
+    LoadUtxoEntries,
+    GenerateSampleUtxo,
+    AddUtxoEntry,
+    RemoveUtxoEntry(usize),
+    UtxoFieldChanged(usize, UtxoField, String),
+    PreviewDatum(usize),
+    CloseDatumPreview,
+    AddAsset(usize),
+    RemoveAsset(usize, String, String),
+    AssetChanged(usize, usize, AssetField, String),
     BrowseInitialUtxo,
+    ValidateUtxoFile,
+    NormalizeUtxoFile,
+    UtxoFileValidation(UtxoValidationEvent),
     PersistenceDirChanged(String),
     BrowsePersistenceDir,
+    LogToFileToggled(bool),
+    LogFileChanged(String),
+    NodeLogLine(String),
+    LogLevelSelected(LogLevel),
+    LogFilterChanged(String),
+    ClearLogs,
+
+    RemovePeer(usize),
+    MovePeerUp(usize),
+    MovePeerDown(usize),
+    ImportPeers,
+    ConfirmImportPeers,
+    ImportPeersFromFile,
+    CancelImportPeers,
+
+    AddEnvVar,
+    RemoveEnvVar(usize),
+    EnvVarChanged(usize, EnvField, String),
+    ExtraArgsChanged(String),
 
     SaveSettings,
+    LoadSettings,
+    ExportBundle,
+    ExportArgs,
+    CopyArgsJson,
+    CopyValue(String),
+    ImportBundle,
+    ConfirmLoad,
+    WatchConfigToggled(bool),
+    ConfigFileChanged(Result<(), String>),
+    CancelLoad,
+    LoadExample,
+    StartFromScratch,
+    ImportConfig,
+    UseTemplate(Template),
+    ToggleDiffSelection(usize),
+    ApplySelectedDiffs,
+    CloseDiffModal,
+    LoadFromEnv,
+    ProfileSelected(NodeId),
+    DuplicateProfile,
+    IncrementOnDuplicateToggled(bool),
+    InstanceOffsetChanged(String),
+    InstanceSuffixChanged(String),
+    CreateNodeInstance,
+    LaunchNode,
+    LaunchPortsChecked(Vec<String>),
+    LaunchNodeAnyway,
+    CancelLaunch,
+    StopNode,
+    ConfirmStopNode,
+    CancelStopNode,
+    AutoRestartToggled(bool),
+    NodeProcessTick(Instant),
+    RestartNode,
+
+    HydraNodeBinaryChanged(String),
+    BrowseHydraNodeBinary,
+    DetectHydraNodeBinary,
+    DismissChangelog,
+    CheckHydraNodeBinary,
+    LoadHydraHelp,
+    HydraHelpLoaded(Result<String, String>),
+    HydraNodeBinaryResult(Result<String, String>),
+    DownloadHydraNode,
+    DownloadProgress(f32),
+    DownloadComplete(Result<PathBuf, String>),
+
+    ResetField(FieldId),
+
+    DebounceTick(Instant),
+    InputSettled(DebouncedField),
+
+    FormScrolled(scrollable::Viewport),
+    PathFieldHovered(PathField),
+    PathFieldLeft(PathField),
+    FileDropped(PathBuf),
+    WindowResized(Size),
+    WindowCloseRequested(window::Id),
+}
+
+impl Message {
+    /// Whether handling the message changes the settings, which a locked form
+    /// refuses.
+    fn edits_settings(&self) -> bool {
+        match self {
+            Message::UtxoTextChanged(action) => action.is_edit(),
+            Message::Undo
+            | Message::Redo
+            | Message::VerbosityToggled(_)
+            | Message::NodeIdChanged(_)
+            | Message::HostChanged(_)
+            | Message::PortChanged(_)
+            | Message::ApiHostChanged(_)
+            | Message::ApiPortChanged(_)
+            | Message::FindFreePort(..)
+            | Message::MonitoringPortChanged(_)
+            | Message::TlsCertPathChanged(_)
+            | Message::TlsKeyPathChanged(_)
+            | Message::BrowseTlsCert
+            | Message::BrowseTlsKey
+            | Message::HydraSigningKeyChanged(_)
+            | Message::AddHydraVerificationKey
+            | Message::RemoveHydraVerificationKey(_)
+            | Message::MoveHydraVerificationKeyUp(_)
+            | Message::MoveHydraVerificationKeyDown(_)
+            | Message::HydraVerificationKeyChanged(..)
+            | Message::BrowseHydraSigningKey
+            | Message::BrowseHydraVerificationKey(_)
+            | Message::ChainConfigTypeChanged(_)
+            | Message::NetworkIdChanged(_)
+            | Message::ApplyNetworkPreset(_)
+            | Message::NodeSocketChanged(_)
+            | Message::HydraScriptsTxIdChanged(_)
+            | Message::CardanoSigningKeyChanged(_)
+            | Message::AddCardanoVerificationKey
+            | Message::RemoveCardanoVerificationKey(_)
+            | Message::MoveCardanoVerificationKeyUp(_)
+            | Message::MoveCardanoVerificationKeyDown(_)
+            | Message::CardanoVerificationKeyChanged(..)
+            | Message::StartChainFromChanged(_)
+            | Message::ContestationPeriodChanged(_)
+            | Message::DepositDeadlineChanged(_)
+            | Message::LedgerGenesisChanged(_)
+            | Message::ProtocolParametersChanged(_)
+            | Message::MinimumContestationPeriodChanged(_)
+            | Message::MaximumTxValidityRangeChanged(_)
+            | Message::ResetHydraParamsToMainnet
+            | Message::InitialUtxoChanged(_)
+            | Message::LoadUtxoEntries
+            | Message::GenerateSampleUtxo
+            | Message::AddUtxoEntry
+            | Message::RemoveUtxoEntry(_)
+            | Message::UtxoFieldChanged(..)
+            | Message::AddAsset(_)
+            | Message::RemoveAsset(..)
+            | Message::AssetChanged(..)
+            | Message::BrowseInitialUtxo
+            | Message::NormalizeUtxoFile
+            | Message::PersistenceDirChanged(_)
+            | Message::BrowsePersistenceDir
+            | Message::LogToFileToggled(_)
+            | Message::LogFileChanged(_)
+            | Message::RemovePeer(_)
+            | Message::MovePeerUp(_)
+            | Message::MovePeerDown(_)
+            | Message::ImportPeers
+            | Message::ConfirmImportPeers
+            | Message::ImportPeersFromFile
+            | Message::AddEnvVar
+            | Message::RemoveEnvVar(_)
+            | Message::EnvVarChanged(..)
+            | Message::ExtraArgsChanged(_)
+            | Message::LoadSettings
+            | Message::ImportBundle
+            | Message::ConfirmLoad
+            | Message::LoadExample
+            | Message::StartFromScratch
+            | Message::ImportConfig
+            | Message::UseTemplate(_)
+            | Message::ApplySelectedDiffs
+            | Message::LoadFromEnv
+            | Message::ProfileSelected(_)
+            | Message::DuplicateProfile
+            | Message::IncrementOnDuplicateToggled(_)
+            | Message::HydraNodeBinaryChanged(_)
+            | Message::BrowseHydraNodeBinary
+            | Message::DetectHydraNodeBinary
+            | Message::DownloadHydraNode
+            | Message::ResetField(_)
+            | Message::FileDropped(_) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Defaults that individual fields are reset to.
+static DEFAULT_RUN_OPTIONS: LazyLock<RunOptions> = LazyLock::new(RunOptions::default);
+
+static NODE_LOGS_SCROLLABLE: LazyLock<scrollable::Id> = LazyLock::new(scrollable::Id::unique);
+
+static FORM_SCROLLABLE: LazyLock<scrollable::Id> = LazyLock::new(scrollable::Id::unique);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FieldId {
+    NodeId,
+    Host,
+    Port,
+    ApiHost,
+    ApiPort,
+    MonitoringPort,
+    TlsCertPath,
+    TlsKeyPath,
+    HydraSigningKey,
+    NetworkId,
+    NodeSocket,
+    HydraScriptsTxId,
+    CardanoSigningKey,
+    StartChainFrom,
+    ContestationPeriod,
+    DepositDeadline,
+    PersistenceDir,
+    Verbosity,
+    InitialUtxoFile,
+    LedgerGenesisFile,
+    ProtocolParametersFile,
+    MinimumContestationPeriod,
+    MaximumTxValidityRange,
+    Peers,
+    HydraVerificationKeys,
+    CardanoVerificationKeys,
+    ChainConfigType,
+}
+
+/// Inputs that take a file or directory dropped onto the window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathField {
+    TlsCert,
+    TlsKey,
+    HydraSigningKey,
+    HydraVerificationKeys,
+    PersistenceDir,
+    NodeSocket,
+    CardanoSigningKey,
+    CardanoVerificationKeys,
+    InitialUtxo,
+    LedgerGenesis,
+    ProtocolParameters,
+}
+
+/// How long a field has to stay untouched before follow-up work runs.
+const INPUT_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// How often a crashed node is relaunched before auto-restart gives up.
+const MAX_RESTARTS: u32 = 5;
+const MAX_RESTART_BACKOFF: Duration = Duration::from_secs(60);
+
+/// How long a stopping node gets to exit before it is killed.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Lines of node output kept for the log panel.
+const MAX_LOG_LINES: usize = 1000;
+
+/// Values of `SessionState::active_section`.
+const BASIC_SECTION: &str = "basic";
+const ADVANCED_SECTION: &str = "advanced";
+
+/// Inputs whose changes trigger filesystem or validation work, which is
+/// deferred until typing pauses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DebouncedField {
+    TlsCertPath,
+    TlsKeyPath,
+    HydraSigningKey,
+    HydraVerificationKey(usize),
+    NodeSocket,
+    InitialUtxoFile,
+    UtxoText,
+    ProtocolParameters,
+    PersistenceDir,
+    ConfigFile,
+    HydraNodeBinary,
+    /// Any other change to the options, which are then validated again.
+    Options,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UtxoField {
+    Reference,
+    Address,
+    Lovelace,
+    Ada,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssetField {
+    PolicyId,
+    AssetName,
+    Quantity,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnvField {
+    Key,
+    Value,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -49,6 +435,15 @@ pub enum ChainConfigType {
     Offline,
     Direct,
 }
+impl ChainConfigType {
+    fn of(chain_config: &ChainConfig) -> Self {
+        match chain_config {
+            ChainConfig::Offline(_) => ChainConfigType::Offline,
+            ChainConfig::Direct(_) => ChainConfigType::Direct,
+        }
+    }
+}
+
 impl Default for ChainConfigType {
     fn default() -> Self {
         ChainConfigType::Offline
@@ -65,80 +460,666 @@ impl std::fmt::Display for ChainConfigType {
 #[derive(Default)]
 pub struct HydraSettings {
     run_options: RunOptions,
+    /// Every other profile by node id; the active one lives in `run_options`.
+    profiles: HashMap<NodeId, RunOptions>,
+    increment_on_duplicate: bool,
     chain_config_type: ChainConfigType,
+    advanced: bool,
+    /// Locked settings cannot be edited until they are unlocked again.
+    locked: bool,
+    history: History,
+    confirm_unlock: bool,
+    /// Masks key paths in the form, e.g. while screen sharing.
+    hide_sensitive: bool,
+    extra_env: Vec<(String, String)>,
+    extra_args_text: String,
+    extra_args_error: Option<String>,
+    /// Text typed into a field that was rejected, with the reason. Dropped on
+    /// the next change to the settings.
+    invalid_inputs: HashMap<FieldId, (String, String)>,
+    node_process: Option<Child>,
+    auto_restart: bool,
+    restart_count: u32,
+    /// Set while a crashed node waits for its backoff to relaunch it.
+    restart_pending: bool,
+    confirm_stop: bool,
+    /// Ports found busy right before a launch, awaiting a decision.
+    port_conflicts: Vec<String>,
+    /// Set once the node was asked to shut down and has not exited yet.
+    stopping_since: Option<Instant>,
+    log_to_file: bool,
+    /// Empty means the default log file in the persistence directory.
+    log_file: String,
+    node_logs: VecDeque<LogLine>,
+    log_level: LogLevel,
+    log_filter: String,
+    status: Option<String>,
+    /// Result of validating the options, refreshed once edits settle.
+    issues: Vec<ValidationIssue>,
+    /// Validity of the TLS certificate, read when its path settles.
+    cert_validity: Option<CertValidity>,
+    /// Why the protocol parameters file is unusable, if it is.
+    protocol_params_error: Option<String>,
+    /// Signing keys that other users can read, with their mode.
+    insecure_keys: HashMap<PathBuf, u32>,
+    pending_inputs: HashMap<DebouncedField, Instant>,
+    config_path: Option<PathBuf>,
+    watch_config: bool,
+    saved_options: RunOptions,
+    /// Fields edited since the options were last saved or loaded.
+    unsaved_changes: RunOptionsDiff,
+    pending_load: Option<PendingLoad>,
+    /// Fields a template or profile would change, waiting for the user to
+    /// pick which to apply.
+    diff_modal: Option<DiffModal>,
+    /// The path field under the cursor, where dropped files go.
+    active_path_field: Option<PathField>,
+    pending_peers: Option<PendingPeers>,
+    hydra_node_binary: String,
+    /// `Some(None)` while a probe is running.
+    api_probe: Option<Option<Result<String, String>>>,
+    show_metrics: bool,
+    metrics: Option<Result<Vec<(&'static str, f64)>, String>>,
+    /// Whether the node socket is a live socket, or why it is not.
+    node_socket_status: Option<Result<(), String>>,
+    confirm_abort: bool,
+    /// Result of the last `--version` run, kept until the binary changes.
+    hydra_node_version: Option<Result<String, String>>,
+    /// The version parsed from `hydra_node_version`, if it is recognized.
+    detected_version: Option<Version>,
+    /// Flags in the options that the detected version does not know.
+    unsupported_flags: Vec<String>,
+    /// The published scripts tx id, when it differs from the one entered.
+    suggested_scripts_tx_id: Option<TxId>,
+    chain_point_issue: Option<ValidationIssue>,
+    checking_version: bool,
+    app_config: AppConfig,
+    /// Window geometry and position in the form, saved when the window closes.
+    session: SessionState,
+    /// Release notes the user has not seen yet.
+    changelog: Option<String>,
+    help_expanded: bool,
+    hydra_params_expanded: bool,
+    /// `hydra-node --help` output, fetched once per session.
+    help_text: Option<String>,
+    download_progress: Option<f32>,
+    key_preview: Option<(PathBuf, Result<KeyPreview, KeyPreviewError>)>,
+    utxo_text: text_editor::Content,
+    utxo_validation: Option<Result<(), String>>,
+    /// Entries checked so far while the UTxO file is being validated.
+    utxo_file_progress: Option<usize>,
+    utxo_file_validation: Option<Result<usize, String>>,
+    utxo_entries: Vec<(String, TxOut)>,
+    /// The ADA amount being typed into an entry, kept as typed until another
+    /// entry or its lovelace is edited.
+    utxo_ada_input: Option<(usize, String)>,
+    datum_preview: Option<DatumPreview>,
+    key_pair_validation: Option<Result<(), String>>,
+    instance_offset: String,
+    instance_suffix: String,
+}
+
+/// Peers read from another node's config, waiting to be confirmed.
+struct PendingPeers {
+    path: PathBuf,
+    peers: Vec<Host>,
+}
+
+/// A loaded config waiting for the user to confirm discarding unsaved edits.
+struct PendingLoad {
+    path: PathBuf,
+    run_options: RunOptions,
+    diff: RunOptionsDiff,
+    /// The file was changed by another program rather than picked by the user.
+    external: bool,
 }
 
 impl HydraSettings {
     pub fn new() -> Self {
-        Self {
+        let app_config = AppConfig::load();
+        let changelog = changelog::pending_changes(app_config.last_seen_version.as_deref());
+        let session = SessionState::load();
+
+        let mut settings = Self {
             run_options: RunOptions::default(),
+            profiles: ProfileStore::load(),
+            increment_on_duplicate: true,
             chain_config_type: ChainConfigType::Direct,
+            advanced: session.active_section == ADVANCED_SECTION,
+            locked: false,
+            history: History::default(),
+            confirm_unlock: false,
+            hide_sensitive: false,
+            extra_env: Vec::new(),
+            extra_args_text: String::new(),
+            extra_args_error: None,
+            invalid_inputs: HashMap::new(),
+            node_process: None,
+            auto_restart: false,
+            restart_count: 0,
+            restart_pending: false,
+            confirm_stop: false,
+            port_conflicts: Vec::new(),
+            stopping_since: None,
+            log_to_file: false,
+            log_file: String::new(),
+            node_logs: VecDeque::new(),
+            log_level: LogLevel::Info,
+            log_filter: String::new(),
+            status: None,
+            issues: Vec::new(),
+            cert_validity: None,
+            protocol_params_error: None,
+            insecure_keys: HashMap::new(),
+            pending_inputs: HashMap::new(),
+            config_path: None,
+            watch_config: false,
+            saved_options: RunOptions::default(),
+            unsaved_changes: RunOptionsDiff::default(),
+            pending_load: None,
+            diff_modal: None,
+            active_path_field: None,
+            pending_peers: None,
+            hydra_node_binary: app_config
+                .hydra_node_binary
+                .clone()
+                .unwrap_or_else(|| HYDRA_NODE_BINARY.to_string()),
+            api_probe: None,
+            show_metrics: false,
+            metrics: None,
+            node_socket_status: None,
+            confirm_abort: false,
+            hydra_node_version: None,
+            detected_version: None,
+            unsupported_flags: Vec::new(),
+            suggested_scripts_tx_id: None,
+            chain_point_issue: None,
+            checking_version: false,
+            app_config,
+            session,
+            changelog,
+            help_expanded: false,
+            hydra_params_expanded: false,
+            help_text: None,
+            download_progress: None,
+            key_preview: None,
+            utxo_text: text_editor::Content::new(),
+            utxo_validation: None,
+            utxo_file_progress: None,
+            utxo_file_validation: None,
+            utxo_entries: Vec::new(),
+            utxo_ada_input: None,
+            datum_preview: None,
+            key_pair_validation: None,
+            instance_offset: "1".to_string(),
+            instance_suffix: "-2".to_string(),
+        };
+
+        if let Some(path) = settings.session.last_config_path.clone() {
+            match RunOptions::load(&path) {
+                Ok(run_options) => settings.apply_loaded(path, run_options),
+                Err(e) => {
+                    tracing::warn!(path = %path.display(), error = %e, "failed to reopen config")
+                }
+            }
         }
+
+        settings.refresh_checks();
+        settings.refresh_derived();
+        settings
+    }
+
+    /// Scrolls the form back to where the previous session left it.
+    pub fn restore_scroll(&self) -> Task<Message> {
+        scrollable::scroll_to(
+            FORM_SCROLLABLE.clone(),
+            scrollable::AbsoluteOffset {
+                x: 0.0,
+                y: self.session.scroll_offset,
+            },
+        )
     }
 
     pub fn view(&self) -> Element<Message> {
-        let content = Column::new()
+        let advanced_toggle =
+            checkbox("Advanced", self.advanced).on_toggle(Message::AdvancedToggled);
+        let hide_sensitive_toggle = checkbox("Hide sensitive fields", self.hide_sensitive)
+            .on_toggle(Message::HideSensitiveToggled);
+
+        let mut content = Column::new().spacing(20).padding(20);
+
+        if let Some(Err(error)) = &self.hydra_node_version {
+            content = content.push(
+                container(text(format!("{}. {}", error, INSTALL_INSTRUCTIONS)))
+                    .padding(10)
+                    .width(Length::Fill)
+                    .style(container::rounded_box),
+            );
+        }
+
+        if let Some(changes) = &self.changelog {
+            content = content.push(changelog::view(changes));
+        }
+
+        if let Some(pending) = &self.pending_load {
+            content = content.push(self.pending_load_section(pending));
+        }
+
+        let lock_controls = if !self.locked {
+            row![button("Lock Settings").on_press(Message::LockSettings)]
+        } else if self.confirm_unlock {
+            row![
+                text("🔒 Locked"),
+                button("Click again to unlock").on_press(Message::UnlockSettings)
+            ]
+        } else {
+            row![
+                text("🔒 Locked"),
+                button("Unlock").on_press(Message::UnlockSettings)
+            ]
+        };
+
+        content = content.push(
+            row![
+                advanced_toggle,
+                hide_sensitive_toggle,
+                lock_controls.spacing(10).align_y(Alignment::Center)
+            ]
             .spacing(20)
-            .padding(20)
+            .align_y(Alignment::Center),
+        );
+
+        let mut form = Column::new()
+            .spacing(20)
+            .push(self.profiles_section())
+            .push(self.hydra_node_section())
+            .push(horizontal_rule(1))
             .push(self.general_settings_section())
             .push(horizontal_rule(1))
             .push(self.api_settings_section())
-            .push(horizontal_rule(1))
-            .push(self.tls_settings_section())
+            .push(horizontal_rule(1));
+
+        if self.advanced {
+            form = form
+                .push(self.tls_settings_section())
+                .push(horizontal_rule(1));
+        }
+
+        form = form
+            .push(self.peers_section())
             .push(horizontal_rule(1))
             .push(self.hydra_keys_section())
             .push(horizontal_rule(1))
             .push(self.chain_config_section())
             .push(horizontal_rule(1))
-            .push(self.persistence_section())
+            .push(self.ledger_config_section())
+            .push(horizontal_rule(1));
+
+        if self.advanced {
+            form = form
+                .push(self.hydra_params_section())
+                .push(horizontal_rule(1))
+                .push(self.persistence_section())
+                .push(horizontal_rule(1))
+                .push(self.environment_section())
+                .push(horizontal_rule(1))
+                .push(self.extra_args_section())
+                .push(horizontal_rule(1));
+        }
+
+        let content = content.push(form);
+
+        let can_undo = !self.locked && self.history.can_undo();
+        let can_redo = !self.locked && self.history.can_redo();
+
+        let actions = row![
+            button("Undo").on_press_maybe(can_undo.then_some(Message::Undo)),
+            button("Redo").on_press_maybe(can_redo.then_some(Message::Redo)),
+            button("Save Settings").on_press(Message::SaveSettings),
+            button("Load Settings").on_press_maybe(self.unlocked(Message::LoadSettings)),
+            button("Load Example").on_press_maybe(self.unlocked(Message::LoadExample)),
+            button("Load from Environment").on_press_maybe(self.unlocked(Message::LoadFromEnv)),
+            button("Export Bundle").on_press(Message::ExportBundle),
+            button("Export Args").on_press(Message::ExportArgs),
+            button("Copy Args as JSON").on_press(Message::CopyArgsJson),
+            button("Import Bundle").on_press_maybe(self.unlocked(Message::ImportBundle)),
+            checkbox("Watch for external changes", self.watch_config)
+                .on_toggle(Message::WatchConfigToggled),
+            button("Launch Node")
+                .on_press_maybe(self.node_process.is_none().then_some(Message::LaunchNode)),
+            button("Stop Node").on_press_maybe(
+                ((self.node_process.is_some() && self.stopping_since.is_none())
+                    || self.restart_pending)
+                    .then_some(Message::StopNode)
+            ),
+            checkbox("Restart on crash", self.auto_restart).on_toggle(Message::AutoRestartToggled),
+            button("Abort Head").on_press(Message::AbortHead)
+        ]
+        .spacing(10);
+
+        let new_instance = row![
+            text("New Node Instance:").width(Length::Fixed(150.0)),
+            text_input("Port offset", &self.instance_offset)
+                .on_input(Message::InstanceOffsetChanged)
+                .width(Length::Fixed(100.0)),
+            text_input("Node ID suffix", &self.instance_suffix)
+                .on_input(Message::InstanceSuffixChanged)
+                .width(Length::Fixed(100.0)),
+            button("Create Instance").on_press(Message::CreateNodeInstance)
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center);
+
+        let mut content = content
+            .push(self.summary_section())
             .push(horizontal_rule(1))
-            .push(button("Save Settings").on_press(Message::SaveSettings));
+            .push(actions);
 
-        let scrollable_content = scrollable(content).height(Length::Fill).width(Length::Fill);
+        if !self.port_conflicts.is_empty() {
+            content = content.push(
+                container(
+                    column![
+                        text(format!(
+                            "{}. hydra-node will fail to bind to it.",
+                            self.port_conflicts.join(". ")
+                        )),
+                        row![
+                            button("Launch Anyway").on_press(Message::LaunchNodeAnyway),
+                            button("Cancel").on_press(Message::CancelLaunch)
+                        ]
+                        .spacing(10)
+                    ]
+                    .spacing(10),
+                )
+                .padding(10)
+                .style(container::rounded_box),
+            );
+        }
 
-        Container::new(scrollable_content)
-            .center_x(Length::Fill)
+        if self.confirm_stop {
+            content = content.push(
+                container(
+                    column![
+                        text(if GRACEFUL_SHUTDOWN {
+                            format!(
+                                "Stop hydra-node? It gets {} seconds to shut down before it \
+                                 is killed.",
+                                SHUTDOWN_TIMEOUT.as_secs()
+                            )
+                        } else {
+                            "Stop hydra-node? It is killed right away on this platform.".to_string()
+                        }),
+                        row![
+                            button("Stop Node").on_press(Message::ConfirmStopNode),
+                            button("Cancel").on_press(Message::CancelStopNode)
+                        ]
+                        .spacing(10)
+                    ]
+                    .spacing(10),
+                )
+                .padding(10)
+                .style(container::rounded_box),
+            );
+        }
+
+        if let Some(since) = self.stopping_since {
+            let elapsed = since.elapsed().min(SHUTDOWN_TIMEOUT);
+            content = content.push(
+                row![
+                    text("Stopping hydra-node..."),
+                    progress_bar(0.0..=SHUTDOWN_TIMEOUT.as_secs_f32(), elapsed.as_secs_f32())
+                        .width(Length::Fixed(200.0))
+                ]
+                .spacing(10)
+                .align_y(Alignment::Center),
+            );
+        }
+
+        if self.confirm_abort {
+            content = content.push(
+                container(
+                    column![
+                        text(format!(
+                            "Send Abort to the head at {}:{}? The head will be closed \
+                             before it opens and committed funds returned.",
+                            self.run_options.api_host, self.run_options.api_port
+                        )),
+                        row![
+                            button("Abort Head").on_press(Message::ConfirmAbortHead),
+                            button("Cancel").on_press(Message::CancelAbortHead)
+                        ]
+                        .spacing(10)
+                    ]
+                    .spacing(10),
+                )
+                .padding(10)
+                .style(container::rounded_box),
+            );
+        }
+
+        content = content.push(new_instance);
+
+        if !self.unsaved_changes.is_empty() {
+            content = content.push(text(format!(
+                "{} unsaved change(s): {}",
+                self.unsaved_changes.len(),
+                self.unsaved_changes
+                    .rows()
+                    .iter()
+                    .map(|(field, _, _)| *field)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )));
+        }
+
+        if let Some(status) = &self.status {
+            content = content.push(text(status));
+        }
+
+        if self.restart_count > 0 {
+            content = content.push(text(format!(
+                "hydra-node restarted {} of {} time(s)",
+                self.restart_count, MAX_RESTARTS
+            )));
+        }
+
+        content = content
+            .push(horizontal_rule(1))
+            .push(self.node_logs_section())
+            .push(horizontal_rule(1))
+            .push(self.hydra_help_section());
+
+        let scrollable_content = scrollable(content)
+            .id(FORM_SCROLLABLE.clone())
+            .on_scroll(Message::FormScrolled)
             .height(Length::Fill)
-            .into()
+            .width(Length::Fill);
+
+        let page = Container::new(scrollable_content)
+            .center_x(Length::Fill)
+            .height(Length::Fill);
+
+        match (&self.diff_modal, &self.datum_preview) {
+            (Some(modal), _) => stack![page, opaque(diff_modal::view(modal))].into(),
+            (None, Some(preview)) => stack![page, opaque(datum_modal::view(preview))].into(),
+            (None, None) => page.into(),
+        }
+    }
+
+    fn node_logs_section(&self) -> Element<Message> {
+        let title = text("Node Logs").size(24);
+
+        let filters = row![
+            text("Minimum level:"),
+            pick_list(
+                LogLevel::ALL,
+                Some(self.log_level),
+                Message::LogLevelSelected
+            ),
+            text_input("Filter lines...", &self.log_filter).on_input(Message::LogFilterChanged),
+            button("Clear")
+                .on_press_maybe((!self.node_logs.is_empty()).then_some(Message::ClearLogs))
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center);
+
+        let lines = self
+            .node_logs
+            .iter()
+            .filter(|line| line.matches(self.log_level, &self.log_filter))
+            .fold(Column::new(), |column, line| {
+                column.push(text(&line.text).font(Font::MONOSPACE).size(12))
+            });
+
+        column![
+            title,
+            filters,
+            // Anchored to the bottom so new output stays in view.
+            scrollable(lines)
+                .id(NODE_LOGS_SCROLLABLE.clone())
+                .anchor_bottom()
+                .height(Length::Fixed(300.0))
+                .width(Length::Fill)
+        ]
+        .spacing(10)
+        .into()
+    }
+
+    fn hydra_help_section(&self) -> Element<Message> {
+        let toggle = button(if self.help_expanded {
+            "Hide Hydra Node Reference"
+        } else {
+            "Show Hydra Node Reference"
+        })
+        .on_press(Message::LoadHydraHelp);
+
+        let mut section = column![toggle].spacing(10);
+
+        if self.help_expanded {
+            section = section.push(match &self.help_text {
+                Some(help) => Element::from(
+                    scrollable(text(help).font(Font::MONOSPACE))
+                        .height(Length::Fixed(300.0))
+                        .width(Length::Fill),
+                ),
+                None => text("Loading...").into(),
+            });
+        }
+
+        section.into()
+    }
+
+    fn hydra_node_section(&self) -> Element<Message> {
+        let title = text("hydra-node").size(24);
+
+        let status = match &self.hydra_node_version {
+            _ if self.checking_version => text("Checking..."),
+            None => text("Not checked yet"),
+            Some(Ok(version)) => match self.detected_version {
+                Some((major, minor, patch)) => {
+                    text(format!("Found version {}.{}.{}", major, minor, patch))
+                }
+                None => text(format!("Warning: unrecognized version \"{}\"", version)),
+            },
+            Some(Err(e)) => text(format!("Warning: {}", e)),
+        };
+
+        let binary = row![
+            text("Binary:").width(Length::Fixed(150.0)),
+            text_input(HYDRA_NODE_BINARY, &self.hydra_node_binary)
+                .on_input_maybe(self.unlocked(Message::HydraNodeBinaryChanged))
+                .width(Length::Fixed(250.0)),
+            button("Browse").on_press_maybe(self.unlocked(Message::BrowseHydraNodeBinary)),
+            button("Detect").on_press_maybe(self.unlocked(Message::DetectHydraNodeBinary)),
+            status,
+            button("Check").on_press(Message::CheckHydraNodeBinary)
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center);
+
+        let mut content = column![title, binary].spacing(10);
+
+        if let Some(Ok(_)) = &self.hydra_node_version {
+            match self.detected_version {
+                Some(_) => {
+                    for warning in &self.unsupported_flags {
+                        content = content.push(text(format!("Warning: {}", warning)));
+                    }
+                }
+                None => {
+                    content = content.push(text("Flag compatibility is unchecked for this version"))
+                }
+            }
+        }
+
+        if let Some(progress) = self.download_progress {
+            content = content.push(progress_bar(0.0..=1.0, progress));
+        } else if let Some(Err(_)) = &self.hydra_node_version {
+            content = content.push(
+                button("Download hydra-node")
+                    .on_press_maybe(self.unlocked(Message::DownloadHydraNode)),
+            );
+        }
+
+        content.into()
     }
 
     fn general_settings_section(&self) -> Element<Message> {
         let title = text("General Settings").size(24);
 
-        let quiet_mode = checkbox(
-            "Quiet Mode",
-            matches!(self.run_options.verbosity, Verbosity::Quiet),
-        )
-        .on_toggle(Message::VerbosityToggled);
+        let quiet_mode = row![
+            self.field_label("Quiet Mode:", FieldId::Verbosity),
+            checkbox("", matches!(self.run_options.verbosity, Verbosity::Quiet))
+                .on_toggle_maybe(self.unlocked(Message::VerbosityToggled)),
+            self.reset_button(FieldId::Verbosity)
+        ]
+        .spacing(10)
+        .align_y(alignment::Alignment::Center);
 
         let node_id = row![
-            text("Node ID:").width(Length::Fixed(150.0)),
+            self.field_label("Node ID:", FieldId::NodeId),
             text_input(
                 "Enter node ID...",
                 match &self.run_options.node_id {
                     NodeId(id) => id,
                 },
             )
-            .on_input(Message::NodeIdChanged)
+            .on_input_maybe(self.unlocked(Message::NodeIdChanged)),
+            copy_button(&self.run_options.node_id.0),
+            self.reset_button(FieldId::NodeId)
         ]
         .spacing(10)
         .align_y(Alignment::Center);
 
         let host = row![
-            text("Host:").width(Length::Fixed(150.0)),
+            self.field_label("Host:", FieldId::Host),
             text_input("Enter host...", &self.run_options.host.to_string(),)
-                .on_input(Message::HostChanged)
+                .on_input_maybe(self.unlocked(Message::HostChanged)),
+            self.reset_button(FieldId::Host)
         ]
         .spacing(10)
         .align_y(Alignment::Center);
 
         let port = row![
-            text("Port:").width(Length::Fixed(150.0)),
-            text_input("Enter port...", &self.run_options.port.to_string(),)
-                .on_input(Message::PortChanged)
+            self.field_label("Port:", FieldId::Port),
+            text_input(
+                "Enter port...",
+                &self.input_text(FieldId::Port, self.run_options.port.to_string()),
+            )
+            .on_input_maybe(self.unlocked(Message::PortChanged)),
+            port_stepper(
+                Some(self.run_options.port),
+                Message::PortChanged,
+                !self.locked
+            ),
+            free_port_button(self.run_options.host, Message::PortChanged, !self.locked),
+            self.reset_button(FieldId::Port)
         ]
         .spacing(10)
         .align_y(Alignment::Center);
+        let port = column![port]
+            .spacing(5)
+            .push_maybe(self.input_error(FieldId::Port));
 
         Column::new()
             .push(title)
@@ -154,93 +1135,357 @@ impl HydraSettings {
         let title = text("API Settings").size(24);
 
         let api_host = row![
-            text("API Host:").width(Length::Fixed(150.0)),
+            self.field_label("API Host:", FieldId::ApiHost),
             text_input("Enter API host...", &self.run_options.api_host.to_string(),)
-                .on_input(Message::ApiHostChanged)
+                .on_input_maybe(self.unlocked(Message::ApiHostChanged)),
+            self.reset_button(FieldId::ApiHost)
         ]
         .spacing(10)
         .align_y(alignment::Alignment::Center);
 
         let api_port = row![
-            text("API Port:").width(Length::Fixed(150.0)),
-            text_input("Enter API port...", &self.run_options.api_port.to_string(),)
-                .on_input(Message::ApiPortChanged)
+            self.field_label("API Port:", FieldId::ApiPort),
+            text_input(
+                "Enter API port...",
+                &self.input_text(FieldId::ApiPort, self.run_options.api_port.to_string()),
+            )
+            .on_input_maybe(self.unlocked(Message::ApiPortChanged)),
+            port_stepper(
+                Some(self.run_options.api_port),
+                Message::ApiPortChanged,
+                !self.locked
+            ),
+            free_port_button(
+                self.run_options.api_host,
+                Message::ApiPortChanged,
+                !self.locked
+            ),
+            self.reset_button(FieldId::ApiPort)
         ]
         .spacing(10)
         .align_y(alignment::Alignment::Center);
+        let api_port = column![api_port]
+            .spacing(5)
+            .push_maybe(self.input_error(FieldId::ApiPort));
 
         let monitoring_port = row![
-            text("Monitoring Port:").width(Length::Fixed(150.0)),
+            self.field_label("Monitoring Port:", FieldId::MonitoringPort),
             text_input(
                 "Enter monitoring port...",
-                self.run_options
-                    .monitoring_port
-                    .map_or("".to_string(), |p| p.to_string())
-                    .as_str(),
+                &self.input_text(
+                    FieldId::MonitoringPort,
+                    self.run_options
+                        .monitoring_port
+                        .map_or("".to_string(), |p| p.to_string()),
+                ),
             )
-            .on_input(Message::MonitoringPortChanged)
+            .on_input_maybe(self.unlocked(Message::MonitoringPortChanged)),
+            port_stepper(
+                self.run_options.monitoring_port,
+                Message::MonitoringPortChanged,
+                !self.locked
+            ),
+            free_port_button(
+                self.run_options.host,
+                Message::MonitoringPortChanged,
+                !self.locked
+            ),
+            self.reset_button(FieldId::MonitoringPort)
         ]
         .spacing(10)
         .align_y(alignment::Alignment::Center);
+        let monitoring_port = column![monitoring_port]
+            .spacing(5)
+            .push_maybe(self.input_error(FieldId::MonitoringPort));
 
-        column![title, api_host, api_port, monitoring_port]
-            .spacing(10)
-            .into()
+        let probe_result = match &self.api_probe {
+            None => text(""),
+            Some(None) => text("Testing..."),
+            Some(Some(Ok(message))) => text(message),
+            Some(Some(Err(e))) => text(e),
+        };
+
+        let test_connection = row![
+            button("Test API Connection").on_press_maybe(
+                (self.api_probe != Some(None)).then_some(Message::TestApiConnection)
+            ),
+            probe_result
+        ]
+        .spacing(10)
+        .align_y(alignment::Alignment::Center);
+
+        let mut content = column![title, api_host, api_port].spacing(10);
+
+        if self.advanced {
+            content = content.push(monitoring_port);
+        }
+
+        content = content.push(test_connection);
+
+        if self.run_options.monitoring_port.is_some() {
+            content = content.push(self.metrics_panel());
+        }
+
+        content.into()
+    }
+
+    fn metrics_panel(&self) -> Element<Message> {
+        let toggle = button(if self.show_metrics {
+            "Hide Metrics"
+        } else {
+            "Show Metrics"
+        })
+        .on_press(Message::MetricsToggled);
+
+        if !self.show_metrics {
+            return toggle.into();
+        }
+
+        let body: Element<Message> = match &self.metrics {
+            None => text("Loading metrics...").into(),
+            Some(Err(_)) if self.node_process.is_none() => {
+                text("hydra-node is not running; metrics appear once it is launched.").into()
+            }
+            Some(Err(e)) => text(e).style(text::danger).into(),
+            Some(Ok(metrics)) if metrics.is_empty() => {
+                text("The node did not report any Hydra head metrics yet.").into()
+            }
+            Some(Ok(metrics)) => metrics
+                .iter()
+                .fold(Column::new().spacing(5), |column, (label, value)| {
+                    column.push(row![
+                        text(*label).width(Length::Fixed(200.0)),
+                        text(value.to_string()).font(Font::MONOSPACE)
+                    ])
+                })
+                .into(),
+        };
+
+        column![toggle, body].spacing(10).into()
     }
 
     fn tls_settings_section(&self) -> Element<Message> {
         let title = text("TLS Settings").size(24);
 
+        let cert_path_text = optional_path(&self.run_options.tls_cert_path);
         let cert_path = row![
-            text("TLS Certificate:").width(Length::Fixed(150.0)),
-            text_input(
-                "Enter certificate path...",
-                &self
-                    .run_options
-                    .tls_cert_path
-                    .as_ref()
-                    .map_or("".to_string(), |p| p.to_string_lossy().to_string()),
-            )
-            .on_input(Message::TlsCertPathChanged),
-            button("Browse").on_press(Message::BrowseTlsCert)
+            self.field_label("TLS Certificate:", FieldId::TlsCertPath),
+            text_input("Enter certificate path...", &cert_path_text)
+                .on_input_maybe(self.unlocked(Message::TlsCertPathChanged)),
+            copy_button(&cert_path_text),
+            button("Browse").on_press_maybe(self.unlocked(Message::BrowseTlsCert)),
+            self.reset_button(FieldId::TlsCertPath)
         ]
         .spacing(10)
         .align_y(alignment::Alignment::Center);
+        let cert_path = drop_target(PathField::TlsCert, cert_path);
 
+        let key_path_text = optional_path(&self.run_options.tls_key_path);
         let key_path = row![
-            text("TLS Key:").width(Length::Fixed(150.0)),
-            text_input(
-                "Enter key path...",
-                &self
-                    .run_options
-                    .tls_key_path
-                    .as_ref()
-                    .map_or("".to_string(), |p| p.to_string_lossy().to_string()),
-            )
-            .on_input(Message::TlsKeyPathChanged),
-            button("Browse").on_press(Message::BrowseTlsKey)
+            self.field_label("TLS Key:", FieldId::TlsKeyPath),
+            text_input("Enter key path...", &key_path_text)
+                .secure(self.hide_sensitive)
+                .on_input_maybe(self.unlocked(Message::TlsKeyPathChanged)),
+            copy_button(&key_path_text),
+            button("Browse").on_press_maybe(self.unlocked(Message::BrowseTlsKey)),
+            self.reset_button(FieldId::TlsKeyPath)
         ]
         .spacing(10)
         .align_y(alignment::Alignment::Center);
+        let key_path = drop_target(PathField::TlsKey, key_path);
+
+        let mut section = column![title, cert_path].spacing(10);
+
+        if self.run_options.tls_cert_path.is_some() {
+            section = section.push(text(match &self.cert_validity {
+                Some(validity) => format!(
+                    "Valid from {} until {}",
+                    validity.not_before, validity.not_after
+                ),
+                None => "Validity: unknown".to_string(),
+            }));
+        }
+
+        section.push(key_path).into()
+    }
+
+    fn peers_section(&self) -> Element<Message> {
+        let title = text("Peers").size(24);
+
+        let peer_count = self.run_options.peers.len();
+        let peers = self.run_options.peers.iter().enumerate().fold(
+            Column::new().spacing(10),
+            |column, (idx, peer)| {
+                column.push(
+                    row![
+                        self.field_label(&format!("Peer {}:", idx + 1), FieldId::Peers),
+                        text(peer.to_string()).width(Length::Fill),
+                        reorder_buttons(
+                            idx,
+                            peer_count,
+                            Message::MovePeerUp,
+                            Message::MovePeerDown,
+                            !self.locked
+                        ),
+                        button("Remove").on_press_maybe(self.unlocked(Message::RemovePeer(idx)))
+                    ]
+                    .spacing(10)
+                    .align_y(alignment::Alignment::Center),
+                )
+            },
+        );
+
+        let mut content = column![title, peers, self.roster_table()].spacing(10);
+
+        if let Some(pending) = &self.pending_peers {
+            let summary = if pending.peers.is_empty() {
+                text(format!(
+                    "{} has no new peers to add.",
+                    pending.path.display()
+                ))
+            } else {
+                text(format!(
+                    "Add {} peer(s) from {}: {}?",
+                    pending.peers.len(),
+                    pending.path.display(),
+                    pending
+                        .peers
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ))
+            };
+
+            content = content.push(summary).push(
+                row![
+                    button("Add").on_press_maybe(
+                        (!pending.peers.is_empty() && !self.locked)
+                            .then_some(Message::ConfirmImportPeers)
+                    ),
+                    button("Cancel").on_press(Message::CancelImportPeers)
+                ]
+                .spacing(10),
+            );
+        } else {
+            content = content.push(
+                row![
+                    button("Import Peers").on_press_maybe(self.unlocked(Message::ImportPeers)),
+                    button("Import Peers From File")
+                        .on_press_maybe(self.unlocked(Message::ImportPeersFromFile))
+                ]
+                .spacing(10),
+            );
+        }
+
+        content.into()
+    }
+
+    /// Lines up every peer with the verification keys at the same position,
+    /// so missing or surplus keys stand out.
+    fn roster_table(&self) -> Element<Message> {
+        let cardano_keys = match &self.run_options.chain_config {
+            ChainConfig::Direct(config) => Some(&config.cardano_verification_keys),
+            ChainConfig::Offline(_) => None,
+        };
+
+        let rows = self
+            .run_options
+            .peers
+            .len()
+            .max(self.run_options.hydra_verification_keys.len())
+            .max(cardano_keys.map_or(0, Vec::len));
 
-        column![title, cert_path, key_path].spacing(10).into()
+        if rows == 0 {
+            return Column::new().into();
+        }
+
+        let missing = || text("(missing)").width(Length::FillPortion(3));
+
+        let mut header = row![
+            text("#").width(Length::Fixed(40.0)),
+            text("Peer").width(Length::FillPortion(2)),
+            text("Hydra Verification Key").width(Length::FillPortion(3))
+        ]
+        .spacing(10);
+        if cardano_keys.is_some() {
+            header = header.push(text("Cardano Verification Key").width(Length::FillPortion(3)));
+        }
+
+        (0..rows)
+            .fold(
+                column![text("Participants").size(18), header].spacing(5),
+                |table, idx| {
+                    let peer = self
+                        .run_options
+                        .peers
+                        .get(idx)
+                        .map_or("(missing)".to_string(), ToString::to_string);
+
+                    let hydra_key: Element<Message> =
+                        match self.run_options.hydra_verification_keys.get(idx) {
+                            Some(key) => {
+                                text_input("Hydra verification key", &key.to_string_lossy())
+                                    .on_input_maybe(self.unlocked(move |value| {
+                                        Message::HydraVerificationKeyChanged(idx, value)
+                                    }))
+                                    .width(Length::FillPortion(3))
+                                    .into()
+                            }
+                            None => missing().into(),
+                        };
+
+                    let mut row = row![
+                        text(format!("{}", idx + 1)).width(Length::Fixed(40.0)),
+                        text(peer).width(Length::FillPortion(2)),
+                        hydra_key
+                    ]
+                    .spacing(10)
+                    .align_y(alignment::Alignment::Center);
+
+                    if let Some(cardano_keys) = cardano_keys {
+                        row = row.push::<Element<Message>>(match cardano_keys.get(idx) {
+                            Some(key) => {
+                                text_input("Cardano verification key", &key.to_string_lossy())
+                                    .on_input_maybe(self.unlocked(move |value| {
+                                        Message::CardanoVerificationKeyChanged(idx, value)
+                                    }))
+                                    .width(Length::FillPortion(3))
+                                    .into()
+                            }
+                            None => missing().into(),
+                        });
+                    }
+
+                    table.push(row)
+                },
+            )
+            .into()
     }
 
     fn hydra_keys_section(&self) -> Element<Message> {
         let title = text("Hydra Keys").size(24);
 
         let signing_key = row![
-            text("Signing Key:").width(Length::Fixed(150.0)),
+            self.field_label("Signing Key:", FieldId::HydraSigningKey),
             text_input(
                 "Enter signing key path...",
                 &self.run_options.hydra_signing_key.to_string_lossy(),
             )
-            .on_input(Message::HydraSigningKeyChanged),
-            button("Browse").on_press(Message::BrowseHydraSigningKey)
+            .secure(self.hide_sensitive)
+            .on_input_maybe(self.unlocked(Message::HydraSigningKeyChanged)),
+            copy_button(&self.run_options.hydra_signing_key.to_string_lossy()),
+            button("Browse").on_press_maybe(self.unlocked(Message::BrowseHydraSigningKey)),
+            button("Preview").on_press(Message::PreviewKey(
+                self.run_options.hydra_signing_key.clone()
+            )),
+            self.reset_button(FieldId::HydraSigningKey)
         ]
         .spacing(10)
         .align_y(alignment::Alignment::Center);
+        let signing_key = drop_target(PathField::HydraSigningKey, signing_key);
 
+        let key_count = self.run_options.hydra_verification_keys.len();
         let verification_keys = self
             .run_options
             .hydra_verification_keys
@@ -249,224 +1494,2895 @@ impl HydraSettings {
             .fold(Column::new().spacing(10), |column, (idx, key)| {
                 column.push(
                     row![
-                        text(format!("Verification Key {}:", idx + 1)).width(Length::Fixed(150.0)),
+                        self.field_label(
+                            &format!("Verification Key {}:", idx + 1),
+                            FieldId::HydraVerificationKeys
+                        ),
                         text_input("Enter verification key path...", &key.to_string_lossy(),)
-                            .on_input(move |value| Message::HydraVerificationKeyChanged(
-                                idx, value
-                            )),
-                        button("Browse").on_press(Message::BrowseHydraVerificationKey(idx)),
-                        button("Remove").on_press(Message::RemoveHydraVerificationKey(idx))
+                            .on_input_maybe(self.unlocked(move |value| {
+                                Message::HydraVerificationKeyChanged(idx, value)
+                            })),
+                        copy_button(&key.to_string_lossy()),
+                        button("Browse").on_press_maybe(
+                            self.unlocked(Message::BrowseHydraVerificationKey(idx))
+                        ),
+                        reorder_buttons(
+                            idx,
+                            key_count,
+                            Message::MoveHydraVerificationKeyUp,
+                            Message::MoveHydraVerificationKeyDown,
+                            !self.locked
+                        ),
+                        button("Remove").on_press_maybe(
+                            self.unlocked(Message::RemoveHydraVerificationKey(idx))
+                        )
                     ]
                     .spacing(10)
                     .align_y(alignment::Alignment::Center),
                 )
             });
 
-        let add_key_button =
-            button("Add Verification Key").on_press(Message::AddHydraVerificationKey);
+        let add_key_button = button("Add Verification Key")
+            .on_press_maybe(self.unlocked(Message::AddHydraVerificationKey));
+        let verification_keys = drop_target(PathField::HydraVerificationKeys, verification_keys);
+        let add_key_button = drop_target(PathField::HydraVerificationKeys, add_key_button);
 
-        column![title, signing_key, verification_keys, add_key_button]
+        let mut content = column![title, signing_key].spacing(10);
+
+        if let Some(warning) = self.key_permissions_warning(&self.run_options.hydra_signing_key) {
+            content = content.push(warning);
+        }
+
+        if let Some(preview) = self.key_preview_for(&self.run_options.hydra_signing_key) {
+            content = content.push(preview);
+        }
+
+        let validate_key_pair = button("Validate Key Pair").on_press_maybe(
+            (!self.run_options.hydra_verification_keys.is_empty())
+                .then_some(Message::ValidateKeyPair),
+        );
+
+        let mut key_pair = row![validate_key_pair]
             .spacing(10)
+            .align_y(alignment::Alignment::Center);
+
+        match &self.key_pair_validation {
+            Some(Ok(())) => key_pair = key_pair.push(text("Signing and verification key match")),
+            Some(Err(e)) => key_pair = key_pair.push(text(e)),
+            None => {}
+        }
+
+        content
+            .push(verification_keys)
+            .push(row![add_key_button, key_pair].spacing(10))
             .into()
     }
 
-    fn chain_config_section(&self) -> Element<Message> {
-        let title = text("Chain Configuration").size(24);
+    fn profiles_section(&self) -> Element<Message> {
+        let mut node_ids: Vec<NodeId> = self.profiles.keys().cloned().collect();
+        node_ids.push(self.run_options.node_id.clone());
+        node_ids.sort_by(|a, b| a.0.cmp(&b.0));
 
-        let config_type = row![
-            text("Configuration Type:").width(Length::Fixed(150.0)),
+        // A pick list cannot be disabled, so a locked form only names the profile.
+        let profile_picker: Element<Message> = if self.locked {
+            text(self.run_options.node_id.to_string()).into()
+        } else {
             pick_list(
-                vec![ChainConfigType::Direct, ChainConfigType::Offline],
-                Some(self.chain_config_type),
-                Message::ChainConfigTypeChanged
+                node_ids,
+                Some(self.run_options.node_id.clone()),
+                Message::ProfileSelected,
             )
-        ]
-        .spacing(10)
+            .into()
+        };
+
+        row![
+            text("Profile:").width(Length::Fixed(150.0)),
+            profile_picker,
+            button("Duplicate").on_press_maybe(self.unlocked(Message::DuplicateProfile)),
+            checkbox("Increment node ID and ports", self.increment_on_duplicate)
+                .on_toggle_maybe(self.unlocked(Message::IncrementOnDuplicateToggled))
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center)
+        .into()
+    }
+
+    fn chain_config_section(&self) -> Element<Message> {
+        let title = text("Chain Configuration").size(24);
+
+        let config_type_picker: Element<Message> = if self.locked {
+            text(self.chain_config_type.to_string()).into()
+        } else {
+            pick_list(
+                vec![ChainConfigType::Direct, ChainConfigType::Offline],
+                Some(self.chain_config_type),
+                Message::ChainConfigTypeChanged,
+            )
+            .into()
+        };
+
+        let config_type = row![
+            self.field_label("Configuration Type:", FieldId::ChainConfigType),
+            config_type_picker
+        ]
+        .spacing(10)
+        .align_y(alignment::Alignment::Center);
+
+        // Only the fields of the selected chain mode are rendered, so Direct-only
+        // and Offline-only settings never show up side by side.
+        let config_fields = match (&self.chain_config_type, &self.run_options.chain_config) {
+            (ChainConfigType::Direct, ChainConfig::Direct(direct_config)) => {
+                self.direct_chain_fields(direct_config)
+            }
+            (ChainConfigType::Offline, ChainConfig::Offline(offline_config)) => {
+                self.offline_chain_fields(offline_config)
+            }
+            _ => column![],
+        };
+
+        column![title, config_type, config_fields]
+            .spacing(10)
+            .into()
+    }
+
+    fn direct_chain_fields(&self, direct_config: &DirectChainConfig) -> Column<Message> {
+        let network_id_text =
+            self.input_text(FieldId::NetworkId, direct_config.network_id.to_string());
+        let network_id = row![
+            self.field_label("Network ID:", FieldId::NetworkId),
+            text_input(
+                "Network magic, mainnet or testnet:<magic>",
+                &network_id_text
+            )
+            .on_input_maybe(self.unlocked(Message::NetworkIdChanged)),
+            self.reset_button(FieldId::NetworkId)
+        ]
+        .spacing(10)
+        .align_y(alignment::Alignment::Center);
+        let network_id = column![network_id]
+            .spacing(5)
+            .push_maybe(self.input_error(FieldId::NetworkId));
+
+        let network_presets = NetworkPreset::ALL.iter().fold(
+            row![Space::with_width(Length::Fixed(150.0))].spacing(10),
+            |presets, preset| {
+                presets.push(
+                    button(text(preset.to_string()))
+                        .on_press_maybe(self.unlocked(Message::ApplyNetworkPreset(*preset))),
+                )
+            },
+        );
+
+        let node_socket = row![
+            self.field_label("Node Socket:", FieldId::NodeSocket),
+            text_input(
+                "Enter node socket path...",
+                &direct_config.node_socket.to_string_lossy(),
+            )
+            .on_input_maybe(self.unlocked(Message::NodeSocketChanged)),
+            copy_button(&direct_config.node_socket.to_string_lossy()),
+            match self.node_socket_status {
+                Some(Ok(())) => text("Socket found").style(text::success),
+                Some(Err(_)) => text("Socket not found").style(text::danger),
+                None => text(""),
+            },
+            self.reset_button(FieldId::NodeSocket)
+        ]
+        .spacing(10)
+        .align_y(alignment::Alignment::Center);
+        let node_socket = drop_target(PathField::NodeSocket, node_socket);
+
+        let node_socket = match &self.node_socket_status {
+            Some(Err(problem)) => column![node_socket, text(problem)].spacing(5),
+            _ => column![node_socket],
+        };
+
+        let scripts_tx_id = row![
+            self.field_label("Scripts Tx ID:", FieldId::HydraScriptsTxId),
+            text_input(
+                "Enter hydra scripts transaction id...",
+                &direct_config.hydra_scripts_tx_id,
+            )
+            .on_input_maybe(self.unlocked(Message::HydraScriptsTxIdChanged)),
+            copy_button(&direct_config.hydra_scripts_tx_id),
+            self.reset_button(FieldId::HydraScriptsTxId)
+        ]
+        .spacing(10)
+        .align_y(alignment::Alignment::Center);
+
+        let scripts_tx_id = match &self.suggested_scripts_tx_id {
+            Some(known) => column![
+                scripts_tx_id,
+                row![
+                    text(format!(
+                        "Warning: the published scripts tx id for this network is {}",
+                        known
+                    )),
+                    button("Use").on_press_maybe(
+                        self.unlocked(Message::HydraScriptsTxIdChanged(known.clone())),
+                    )
+                ]
+                .spacing(10)
+                .align_y(alignment::Alignment::Center)
+            ]
+            .spacing(5),
+            None => column![scripts_tx_id],
+        };
+
+        let signing_key = row![
+            self.field_label("Cardano Signing Key:", FieldId::CardanoSigningKey),
+            text_input(
+                "Enter cardano signing key path...",
+                &direct_config.cardano_signing_key.to_string_lossy(),
+            )
+            .secure(self.hide_sensitive)
+            .on_input_maybe(self.unlocked(Message::CardanoSigningKeyChanged)),
+            copy_button(&direct_config.cardano_signing_key.to_string_lossy()),
+            button("Preview").on_press(Message::PreviewKey(
+                direct_config.cardano_signing_key.clone()
+            )),
+            self.reset_button(FieldId::CardanoSigningKey)
+        ]
+        .spacing(10)
+        .align_y(alignment::Alignment::Center);
+        let signing_key = drop_target(PathField::CardanoSigningKey, signing_key);
+
+        let key_count = direct_config.cardano_verification_keys.len();
+        let verification_keys = direct_config
+            .cardano_verification_keys
+            .iter()
+            .enumerate()
+            .fold(Column::new().spacing(10), |column, (idx, key)| {
+                column.push(
+                    row![
+                        self.field_label(
+                            &format!("Cardano Key {}:", idx + 1),
+                            FieldId::CardanoVerificationKeys
+                        ),
+                        text_input("Enter verification key path...", &key.to_string_lossy())
+                            .on_input_maybe(self.unlocked(move |value| {
+                                Message::CardanoVerificationKeyChanged(idx, value)
+                            })),
+                        copy_button(&key.to_string_lossy()),
+                        reorder_buttons(
+                            idx,
+                            key_count,
+                            Message::MoveCardanoVerificationKeyUp,
+                            Message::MoveCardanoVerificationKeyDown,
+                            !self.locked
+                        ),
+                        button("Remove").on_press_maybe(
+                            self.unlocked(Message::RemoveCardanoVerificationKey(idx))
+                        )
+                    ]
+                    .spacing(10)
+                    .align_y(alignment::Alignment::Center),
+                )
+            });
+
+        let add_key_button = button("Add Cardano Verification Key")
+            .on_press_maybe(self.unlocked(Message::AddCardanoVerificationKey));
+        let verification_keys = drop_target(PathField::CardanoVerificationKeys, verification_keys);
+        let add_key_button = drop_target(PathField::CardanoVerificationKeys, add_key_button);
+
+        let contestation_period = row![
+            self.field_label("Contestation Period:", FieldId::ContestationPeriod),
+            text_input(
+                "Enter contestation period...",
+                &direct_config.contestation_period.to_string(),
+            )
+            .on_input_maybe(self.unlocked(Message::ContestationPeriodChanged)),
+            self.reset_button(FieldId::ContestationPeriod)
+        ]
+        .spacing(10)
+        .align_y(alignment::Alignment::Center);
+
+        let mut fields = column![
+            network_id,
+            network_presets,
+            node_socket,
+            scripts_tx_id,
+            signing_key
+        ]
+        .spacing(10);
+
+        if let Some(warning) = self.key_permissions_warning(&direct_config.cardano_signing_key) {
+            fields = fields.push(warning);
+        }
+
+        if let Some(preview) = self.key_preview_for(&direct_config.cardano_signing_key) {
+            fields = fields.push(preview);
+        }
+
+        fields = fields
+            .push(verification_keys)
+            .push(add_key_button)
+            .push(contestation_period);
+
+        if self.advanced {
+            let start_chain_from = row![
+                self.field_label("Start Chain From:", FieldId::StartChainFrom),
+                text_input(
+                    "<slot>.<block hash>",
+                    direct_config.start_chain_from.as_deref().unwrap_or(""),
+                )
+                .on_input_maybe(self.unlocked(Message::StartChainFromChanged)),
+                self.reset_button(FieldId::StartChainFrom)
+            ]
+            .spacing(10)
+            .align_y(alignment::Alignment::Center);
+
+            let start_chain_from = column![start_chain_from].spacing(5).push_maybe(
+                self.chain_point_issue
+                    .as_ref()
+                    .map(|issue| match issue.severity {
+                        Severity::Error => text(&issue.message).style(text::danger),
+                        Severity::Warning => text(format!("Warning: {}", issue.message)),
+                    }),
+            );
+
+            let deposit_deadline = row![
+                self.field_label("Deposit Deadline:", FieldId::DepositDeadline),
+                text_input(
+                    "Enter deposit deadline...",
+                    &direct_config.deposit_deadline.to_string(),
+                )
+                .on_input_maybe(self.unlocked(Message::DepositDeadlineChanged)),
+                self.reset_button(FieldId::DepositDeadline)
+            ]
+            .spacing(10)
+            .align_y(alignment::Alignment::Center);
+
+            fields = fields.push(start_chain_from).push(deposit_deadline);
+        }
+
+        fields
+    }
+
+    fn offline_chain_fields(&self, offline_config: &OfflineChainConfig) -> Column<Message> {
+        let initial_utxo = row![
+            self.field_label("Initial UTxO File:", FieldId::InitialUtxoFile),
+            text_input(
+                "Enter UTxO file path...",
+                &offline_config.initial_utxo_file.to_string_lossy(),
+            )
+            .on_input_maybe(self.unlocked(Message::InitialUtxoChanged)),
+            copy_button(&offline_config.initial_utxo_file.to_string_lossy()),
+            button("Browse").on_press_maybe(self.unlocked(Message::BrowseInitialUtxo)),
+            button("Validate File").on_press_maybe(
+                self.utxo_file_progress
+                    .is_none()
+                    .then_some(Message::ValidateUtxoFile)
+            ),
+            button("Normalize File").on_press_maybe(self.unlocked(Message::NormalizeUtxoFile)),
+            self.reset_button(FieldId::InitialUtxoFile)
+        ]
+        .spacing(10)
+        .align_y(alignment::Alignment::Center);
+        let initial_utxo = drop_target(PathField::InitialUtxo, initial_utxo);
+
+        let utxo_file_status = match (self.utxo_file_progress, &self.utxo_file_validation) {
+            (Some(count), _) => Some(text(format!("Validating... {} entries checked", count))),
+            (None, Some(Ok(count))) => {
+                Some(text(format!("UTxO file is valid ({} entries)", count)))
+            }
+            (None, Some(Err(e))) => {
+                Some(text(format!("Invalid UTxO file: {}", e)).style(text::danger))
+            }
+            (None, None) => None,
+        };
+
+        let ledger_genesis_text = optional_path(&offline_config.ledger_genesis_file);
+        let ledger_genesis = row![
+            self.field_label("Ledger Genesis:", FieldId::LedgerGenesisFile),
+            text_input("Enter ledger genesis file path...", &ledger_genesis_text)
+                .on_input_maybe(self.unlocked(Message::LedgerGenesisChanged)),
+            copy_button(&ledger_genesis_text),
+            self.reset_button(FieldId::LedgerGenesisFile)
+        ]
+        .spacing(10)
+        .align_y(alignment::Alignment::Center);
+        let ledger_genesis = drop_target(PathField::LedgerGenesis, ledger_genesis);
+
+        // Without an action handler the editor is read-only.
+        let mut utxo_editor = text_editor(&self.utxo_text)
+            .placeholder(
+                "Or paste the initial UTxO JSON here; it is written to the file above on save",
+            )
+            .height(Length::Fixed(200.0));
+        if !self.locked {
+            utxo_editor = utxo_editor.on_action(Message::UtxoTextChanged);
+        }
+
+        let mut fields = column![initial_utxo]
+            .push_maybe(utxo_file_status)
+            .push(ledger_genesis)
+            .push(self.utxo_entries_table())
+            .spacing(10);
+
+        if self.utxo_entries.is_empty() {
+            fields = fields.push(utxo_editor).push(
+                button("Generate Sample UTxO")
+                    .on_press_maybe(self.unlocked(Message::GenerateSampleUtxo)),
+            );
+
+            match &self.utxo_validation {
+                Some(Ok(())) => fields = fields.push(text("UTxO JSON is valid")),
+                Some(Err(e)) => fields = fields.push(text(format!("Invalid UTxO JSON: {}", e))),
+                None => {}
+            }
+        }
+
+        fields
+    }
+
+    /// Editable rows for the initial UTxO. When it has entries, they replace
+    /// the pasted JSON as what gets written on save.
+    fn utxo_entries_table(&self) -> Element<Message> {
+        let header = row![
+            text("UTxO Ref").width(Length::FillPortion(3)),
+            text("Address").width(Length::FillPortion(3)),
+            text("Lovelace").width(Length::FillPortion(1)),
+            text("ADA").width(Length::FillPortion(1)),
+            text("").width(Length::Fixed(190.0))
+        ]
+        .spacing(10);
+
+        let rows = self.utxo_entries.iter().enumerate().fold(
+            Column::new().spacing(5),
+            |column, (idx, (reference, tx_out))| {
+                let entry = row![
+                    text_input("<tx id>#<index>", reference)
+                        .on_input_maybe(self.unlocked(move |value| {
+                            Message::UtxoFieldChanged(idx, UtxoField::Reference, value)
+                        }))
+                        .width(Length::FillPortion(3)),
+                    text_input("addr_test1...", &tx_out.address)
+                        .on_input_maybe(self.unlocked(move |value| {
+                            Message::UtxoFieldChanged(idx, UtxoField::Address, value)
+                        }))
+                        .width(Length::FillPortion(3)),
+                    address_badge(&tx_out.address),
+                    text_input("0", &tx_out.value.lovelace.to_string())
+                        .on_input_maybe(self.unlocked(move |value| {
+                            Message::UtxoFieldChanged(idx, UtxoField::Lovelace, value)
+                        }))
+                        .width(Length::FillPortion(1)),
+                    text_input("0.000000", &self.utxo_ada_value(idx, tx_out))
+                        .on_input_maybe(self.unlocked(move |value| {
+                            Message::UtxoFieldChanged(idx, UtxoField::Ada, value)
+                        }))
+                        .width(Length::FillPortion(1)),
+                    button("Add Asset")
+                        .on_press_maybe(self.unlocked(Message::AddAsset(idx)))
+                        .width(Length::Fixed(100.0)),
+                    button("Remove")
+                        .on_press_maybe(self.unlocked(Message::RemoveUtxoEntry(idx)))
+                        .width(Length::Fixed(80.0))
+                ]
+                .spacing(10)
+                .align_y(alignment::Alignment::Center);
+
+                column
+                    .push(entry)
+                    .push_maybe(tx_out.inline_datum().map(|_| {
+                        container(
+                            button("Preview Datum")
+                                .style(button::text)
+                                .on_press(Message::PreviewDatum(idx)),
+                        )
+                        .padding([0, 40])
+                    }))
+                    .push(utxo_assets_table(
+                        idx,
+                        &tx_out.value.asset_list(),
+                        !self.locked,
+                    ))
+            },
+        );
+
+        let buttons = row![
+            button("Add Entry").on_press_maybe(self.unlocked(Message::AddUtxoEntry)),
+            button("Load Entries From File")
+                .on_press_maybe(self.unlocked(Message::LoadUtxoEntries))
+        ]
+        .spacing(10);
+
+        let mut table = Column::new().spacing(10);
+        if !self.utxo_entries.is_empty() {
+            let total = self
+                .utxo_entries
+                .iter()
+                .map(|(_, tx_out)| tx_out.value.lovelace)
+                .fold(0u64, u64::saturating_add);
+
+            table = table
+                .push(header)
+                .push(rows)
+                .push(text(format!("Total: {}", format_ada(total))));
+        }
+
+        table.push(buttons).into()
+    }
+
+    /// The ADA amount shown next to an entry's lovelace: what is being typed
+    /// into it, or its lovelace converted.
+    fn utxo_ada_value(&self, idx: usize, tx_out: &TxOut) -> String {
+        match &self.utxo_ada_input {
+            Some((editing, value)) if *editing == idx => value.clone(),
+            _ => format!("{:.6}", lovelace_to_ada(tx_out.value.lovelace)),
+        }
+    }
+
+    fn ledger_config_section(&self) -> Element<Message> {
+        let title = text("Ledger Configuration").size(24);
+        let path = &self
+            .run_options
+            .ledger_config
+            .cardano_ledger_protocol_parameters_file;
+
+        let protocol_parameters = row![
+            self.field_label("Protocol Parameters:", FieldId::ProtocolParametersFile),
+            text_input(
+                "Enter protocol parameters file path...",
+                &path.to_string_lossy()
+            )
+            .on_input_maybe(self.unlocked(Message::ProtocolParametersChanged)),
+            copy_button(&path.to_string_lossy()),
+            self.reset_button(FieldId::ProtocolParametersFile)
+        ]
+        .spacing(10)
+        .align_y(alignment::Alignment::Center);
+        let protocol_parameters = drop_target(PathField::ProtocolParameters, protocol_parameters);
+
+        let mut section = column![title, protocol_parameters].spacing(10);
+
+        if let Some(e) = &self.protocol_params_error {
+            section = section.push(text(e));
+        }
+
+        section.into()
+    }
+
+    fn hydra_params_section(&self) -> Element<Message> {
+        let toggle = button(if self.hydra_params_expanded {
+            "Hide Hydra Protocol Parameters"
+        } else {
+            "Show Hydra Protocol Parameters"
+        })
+        .on_press(Message::HydraParamsToggled);
+
+        if !self.hydra_params_expanded {
+            return toggle.into();
+        }
+
+        let params = &self.run_options.hydra_protocol_params;
+        let optional = |value: Option<u64>| value.map_or(String::new(), |v| v.to_string());
+
+        let minimum_contestation_period = row![
+            self.field_label(
+                "Min. Contestation Period:",
+                FieldId::MinimumContestationPeriod
+            ),
+            text_input(
+                "hydra-node default",
+                &optional(params.minimum_contestation_period)
+            )
+            .on_input_maybe(self.unlocked(Message::MinimumContestationPeriodChanged)),
+            text("seconds"),
+            self.reset_button(FieldId::MinimumContestationPeriod)
+        ]
+        .spacing(10)
+        .align_y(alignment::Alignment::Center);
+
+        let maximum_tx_validity_range = row![
+            self.field_label("Max. Tx Validity Range:", FieldId::MaximumTxValidityRange),
+            text_input(
+                "hydra-node default",
+                &optional(params.maximum_tx_validity_range)
+            )
+            .on_input_maybe(self.unlocked(Message::MaximumTxValidityRangeChanged)),
+            text("seconds"),
+            self.reset_button(FieldId::MaximumTxValidityRange)
+        ]
+        .spacing(10)
+        .align_y(alignment::Alignment::Center);
+
+        column![
+            toggle,
+            text("Hydra Protocol Parameters").size(24),
+            minimum_contestation_period,
+            maximum_tx_validity_range,
+            button("Reset to Mainnet defaults")
+                .on_press_maybe(self.unlocked(Message::ResetHydraParamsToMainnet))
+        ]
+        .spacing(10)
+        .into()
+    }
+
+    fn persistence_section(&self) -> Element<Message> {
+        let title = text("Persistence Settings").size(24);
+
+        let dir_path = row![
+            self.field_label("Persistence Directory:", FieldId::PersistenceDir),
+            text_input(
+                "Enter persistence directory path...",
+                &self.run_options.persistence_dir.to_string_lossy(),
+            )
+            .on_input_maybe(self.unlocked(Message::PersistenceDirChanged)),
+            copy_button(&self.run_options.persistence_dir.to_string_lossy()),
+            button("Browse").on_press_maybe(self.unlocked(Message::BrowsePersistenceDir)),
+            self.reset_button(FieldId::PersistenceDir)
+        ]
+        .spacing(10)
+        .align_y(alignment::Alignment::Center);
+        let dir_path = drop_target(PathField::PersistenceDir, dir_path);
+
+        let default_log = default_log_path(&self.run_options.persistence_dir);
+        let log_file = row![
+            checkbox("Write node output to a log file", self.log_to_file)
+                .on_toggle_maybe(self.unlocked(Message::LogToFileToggled)),
+            text_input(&default_log.to_string_lossy(), &self.log_file).on_input_maybe(
+                (self.log_to_file && !self.locked).then_some(Message::LogFileChanged)
+            )
+        ]
+        .spacing(10)
         .align_y(alignment::Alignment::Center);
 
-        let config_fields = match self.chain_config_type {
-            ChainConfigType::Direct => {
-                if let ChainConfig::Direct(direct_config) = &self.run_options.chain_config {
-                    column![
-                        row![
-                            text("Network ID:").width(Length::Fixed(150.0)),
-                            text_input(
-                                "Enter network ID...",
-                                &direct_config.network_id.to_string(),
-                            )
-                            .on_input(Message::NetworkIdChanged)
-                        ],
-                        row![
-                            text("Node Socket:").width(Length::Fixed(150.0)),
-                            text_input(
-                                "Enter node socket path...",
-                                &direct_config.node_socket.to_string_lossy(),
-                            )
-                            .on_input(Message::NodeSocketChanged)
-                        ]
-                    ]
-                    .spacing(10)
-                } else {
-                    column![]
-                }
-            }
-            ChainConfigType::Offline => {
-                if let ChainConfig::Offline(offline_config) = &self.run_options.chain_config {
-                    column![row![
-                        text("Initial UTxO File:").width(Length::Fixed(150.0)),
-                        text_input(
-                            "Enter UTxO file path...",
-                            &offline_config.initial_utxo_file.to_string_lossy(),
-                        )
-                        .on_input(Message::InitialUtxoChanged),
-                        button("Browse").on_press(Message::BrowseInitialUtxo)
-                    ]]
-                    .spacing(10)
-                } else {
-                    column![]
-                }
+        column![title, dir_path, log_file].spacing(10).into()
+    }
+
+    fn pending_load_section(&self, pending: &PendingLoad) -> Element<Message> {
+        let title = if pending.external {
+            text(format!(
+                "{} was changed outside Juno. Reload it? {} field(s) will change:",
+                pending.path.display(),
+                pending.diff.len()
+            ))
+        } else {
+            text(format!(
+                "Loading {} will discard unsaved changes. {} field(s) will change:",
+                pending.path.display(),
+                pending.diff.len()
+            ))
+        };
+
+        let fields = text(pending.diff.to_string()).font(Font::MONOSPACE);
+
+        let buttons = row![
+            button("Load").on_press_maybe(self.unlocked(Message::ConfirmLoad)),
+            button("Cancel").on_press(Message::CancelLoad)
+        ]
+        .spacing(10);
+
+        container(column![title, fields, buttons].spacing(10))
+            .padding(10)
+            .style(container::rounded_box)
+            .into()
+    }
+
+    /// A warning row with a fix action, shown when the signing key at `path`
+    /// is readable by group or others.
+    fn key_permissions_warning(&self, path: &Path) -> Option<Element<Message>> {
+        let mode = self.insecure_keys.get(path)?;
+
+        Some(
+            row![
+                text(format!(
+                    "{} is readable by other users (mode {:o}).",
+                    path.display(),
+                    mode
+                )),
+                button("Fix Permissions").on_press(Message::FixKeyPermissions(path.to_path_buf()))
+            ]
+            .spacing(10)
+            .align_y(alignment::Alignment::Center)
+            .into(),
+        )
+    }
+
+    /// The open key preview, if it belongs to the key at `path`.
+    fn key_preview_for(&self, path: &Path) -> Option<Element<Message>> {
+        match &self.key_preview {
+            Some((preview_path, preview)) if preview_path == path => {
+                Some(key_preview::view(preview_path, preview))
+            }
+            _ => None,
+        }
+    }
+
+    /// A field label, drawn in bold when the value differs from its default.
+    fn field_label(&self, label: &str, field: FieldId) -> Element<Message> {
+        let flag = flag_for_label(label);
+        let mut label = text(label.to_string()).width(Length::Fixed(150.0));
+
+        if !self.is_default(field) {
+            label = label.font(Font {
+                weight: font::Weight::Bold,
+                ..Font::DEFAULT
+            });
+        }
+
+        match flag {
+            Some(flag) => tooltip(
+                label,
+                container(text(flag).font(Font::MONOSPACE))
+                    .padding(5)
+                    .style(container::rounded_box),
+                tooltip::Position::Right,
+            )
+            .into(),
+            None => label.into(),
+        }
+    }
+
+    fn reset_button(&self, field: FieldId) -> Element<Message> {
+        button(text("↺").size(14))
+            .padding([2, 8])
+            .on_press_maybe(
+                (!self.locked && !self.is_default(field)).then_some(Message::ResetField(field)),
+            )
+            .into()
+    }
+
+    /// `message` while the settings can be edited, so locked inputs are disabled.
+    fn unlocked<T>(&self, message: T) -> Option<T> {
+        (!self.locked).then_some(message)
+    }
+
+    fn is_default(&self, field: FieldId) -> bool {
+        is_default_field(&self.run_options, field)
+    }
+
+    fn environment_section(&self) -> Element<Message> {
+        let title = text("Environment Variables").size(24);
+
+        let variables = self.extra_env.iter().enumerate().fold(
+            Column::new().spacing(10),
+            |column, (idx, (key, value))| {
+                column.push(
+                    row![
+                        text_input("Name", key)
+                            .on_input_maybe(self.unlocked(move |key| Message::EnvVarChanged(
+                                idx,
+                                EnvField::Key,
+                                key
+                            )))
+                            .width(Length::Fixed(200.0)),
+                        text_input("Value", value).on_input_maybe(self.unlocked(move |value| {
+                            Message::EnvVarChanged(idx, EnvField::Value, value)
+                        })),
+                        button("Remove").on_press_maybe(self.unlocked(Message::RemoveEnvVar(idx)))
+                    ]
+                    .spacing(10)
+                    .align_y(alignment::Alignment::Center),
+                )
+            },
+        );
+
+        let add_button = button("Add").on_press_maybe(self.unlocked(Message::AddEnvVar));
+
+        column![title, variables, add_button].spacing(10).into()
+    }
+
+    fn extra_args_section(&self) -> Element<Message> {
+        let title = text("Extra Arguments").size(24);
+
+        let input = text_input("--some-new-flag value", &self.extra_args_text)
+            .on_input_maybe(self.unlocked(Message::ExtraArgsChanged))
+            .font(Font::MONOSPACE);
+
+        let mut section = column![
+            title,
+            text("Appended verbatim to the hydra-node command line."),
+            input
+        ]
+        .spacing(10);
+
+        if let Some(error) = &self.extra_args_error {
+            section = section.push(text(format!("Invalid arguments: {}", error)));
+        }
+
+        section.into()
+    }
+
+    fn summary_section(&self) -> Element<Message> {
+        let title = text("Summary").size(24);
+
+        let issue_list = if self.issues.is_empty() {
+            Column::new().push(text("No issues found."))
+        } else {
+            self.issues
+                .iter()
+                .fold(Column::new().spacing(5), |column, issue| {
+                    let label = match issue.severity {
+                        Severity::Warning => text(format!("Warning: {}", issue.message)),
+                        Severity::Error => text(format!("Error: {}", issue.message)),
+                    };
+                    column.push(label)
+                })
+        };
+
+        column![title, issue_list].spacing(10).into()
+    }
+
+    pub fn update(&mut self, message: Message) -> Task<Message> {
+        // Disabled inputs cannot send these, but shortcuts, drops and inputs
+        // that had focus before locking still can.
+        if self.locked && message.edits_settings() {
+            self.status = Some("Settings are locked; unlock them to make changes".to_string());
+            return Task::none();
+        }
+
+        let started = Instant::now();
+        let before = self.run_options.clone();
+        let edit = (!matches!(message, Message::Undo | Message::Redo))
+            .then(|| mem::discriminant(&message));
+        let task = self.handle(message);
+
+        if self.run_options != before {
+            self.refresh_derived();
+            self.invalid_inputs.clear();
+            if let Some(edit) = edit {
+                self.history.record(before, edit);
+            }
+            // Inputs that debounce themselves run their checks once they
+            // settle; anything else re-checks the options as a whole.
+            if !self
+                .pending_inputs
+                .values()
+                .any(|edited_at| *edited_at >= started)
+            {
+                self.debounce(DebouncedField::Options);
+            }
+        }
+        task
+    }
+
+    fn handle(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::LockSettings => {
+                self.locked = true;
+                self.confirm_unlock = false;
+            }
+            Message::UnlockSettings => {
+                if self.confirm_unlock {
+                    self.locked = false;
+                }
+                self.confirm_unlock = !self.confirm_unlock;
+            }
+            Message::AdvancedToggled(advanced) => {
+                self.advanced = advanced;
+            }
+            Message::Undo => {
+                if let Some(previous) = self.history.undo(self.run_options.clone()) {
+                    self.start_from(previous);
+                }
+            }
+            Message::Redo => {
+                if let Some(next) = self.history.redo(self.run_options.clone()) {
+                    self.start_from(next);
+                }
+            }
+            Message::PathFieldHovered(field) => {
+                self.active_path_field = Some(field);
+            }
+            // The next field may already have been entered, so only the field
+            // being left is cleared.
+            Message::PathFieldLeft(field) => {
+                if self.active_path_field == Some(field) {
+                    self.active_path_field = None;
+                }
+            }
+            Message::FileDropped(path) => match self.active_path_field {
+                Some(field) => return self.drop_file(field, path),
+                None => {
+                    self.status = Some("Drop files onto a path field".to_string());
+                }
+            },
+            Message::FormScrolled(viewport) => {
+                self.session.scroll_offset = viewport.absolute_offset().y;
+            }
+            Message::WindowResized(size) => {
+                self.session.window_width = size.width;
+                self.session.window_height = size.height;
+            }
+            Message::WindowCloseRequested(id) => {
+                self.session.last_config_path = self.config_path.clone();
+                self.session.active_section = if self.advanced {
+                    ADVANCED_SECTION.to_string()
+                } else {
+                    BASIC_SECTION.to_string()
+                };
+                if let Err(e) = self.session.save() {
+                    tracing::warn!(error = %e, "failed to save session");
+                }
+                return window::close(id);
+            }
+            Message::HideSensitiveToggled(hide) => {
+                self.hide_sensitive = hide;
+            }
+            Message::VerbosityToggled(quiet) => {
+                self.run_options.verbosity = if quiet {
+                    Verbosity::Quiet
+                } else {
+                    Verbosity::Verbose
+                };
+            }
+            Message::NodeIdChanged(id) => {
+                self.run_options.node_id = NodeId(id);
+            }
+            Message::HostChanged(host) => {
+                if let Ok(ip) = host.parse() {
+                    self.run_options.host = ip;
+                }
+            }
+            Message::PortChanged(port) => match parse_port(&port) {
+                Ok(p) => self.run_options.port = p,
+                Err(e) => self.reject_input(FieldId::Port, port, e),
+            },
+            Message::ApiHostChanged(host) => {
+                if let Ok(ip) = host.parse() {
+                    self.run_options.api_host = ip;
+                }
+            }
+            Message::ApiPortChanged(port) => match parse_port(&port) {
+                Ok(p) => self.run_options.api_port = p,
+                Err(e) => self.reject_input(FieldId::ApiPort, port, e),
+            },
+            Message::MonitoringPortChanged(port) if port.trim().is_empty() => {
+                self.run_options.monitoring_port = None;
+            }
+            Message::MonitoringPortChanged(port) => match parse_port(&port) {
+                Ok(p) => self.run_options.monitoring_port = Some(p),
+                Err(e) => self.reject_input(FieldId::MonitoringPort, port, e),
+            },
+            Message::TlsCertPathChanged(path) => {
+                self.run_options.tls_cert_path = Some(PathBuf::from(path));
+                self.debounce(DebouncedField::TlsCertPath);
+            }
+            Message::TlsKeyPathChanged(path) => {
+                self.run_options.tls_key_path = Some(PathBuf::from(path));
+                self.debounce(DebouncedField::TlsKeyPath);
+            }
+            Message::HydraSigningKeyChanged(path) => {
+                self.run_options.hydra_signing_key = PathBuf::from(path);
+                self.debounce(DebouncedField::HydraSigningKey);
+            }
+            Message::AddHydraVerificationKey => {
+                self.run_options
+                    .hydra_verification_keys
+                    .push(PathBuf::new());
+            }
+            Message::RemoveHydraVerificationKey(idx) => {
+                self.run_options.hydra_verification_keys.remove(idx);
+            }
+            Message::MoveHydraVerificationKeyUp(idx) => {
+                move_up(&mut self.run_options.hydra_verification_keys, idx);
+            }
+            Message::MoveHydraVerificationKeyDown(idx) => {
+                move_down(&mut self.run_options.hydra_verification_keys, idx);
+            }
+            Message::HydraVerificationKeyChanged(idx, path) => {
+                if let Some(key) = self.run_options.hydra_verification_keys.get_mut(idx) {
+                    *key = PathBuf::from(path);
+                    self.debounce(DebouncedField::HydraVerificationKey(idx));
+                }
+            }
+            Message::ChainConfigTypeChanged(config_type) => {
+                self.chain_config_type = config_type;
+                self.run_options.chain_config = match config_type {
+                    ChainConfigType::Direct => ChainConfig::Direct(DirectChainConfig::default()),
+                    ChainConfigType::Offline => ChainConfig::Offline(OfflineChainConfig::default()),
+                };
+            }
+            Message::NetworkIdChanged(value) => {
+                if let ChainConfig::Direct(config) = &mut self.run_options.chain_config {
+                    match value.parse() {
+                        Ok(network_id) => config.network_id = network_id,
+                        Err(e) => self.reject_input(FieldId::NetworkId, value, e),
+                    }
+                }
+            }
+            Message::ApplyNetworkPreset(preset) => {
+                if let ChainConfig::Direct(config) = &mut self.run_options.chain_config {
+                    config.network_id = preset.network_id();
+                }
+            }
+            Message::NodeSocketChanged(socket) => {
+                if let ChainConfig::Direct(config) = &mut self.run_options.chain_config {
+                    config.node_socket = PathBuf::from(socket);
+                    self.node_socket_status = None;
+                    self.debounce(DebouncedField::NodeSocket);
+                }
+            }
+            Message::NodeSocketStatus(status) => {
+                self.node_socket_status = Some(status);
+            }
+            Message::HydraScriptsTxIdChanged(tx_id) => {
+                if let ChainConfig::Direct(config) = &mut self.run_options.chain_config {
+                    config.hydra_scripts_tx_id = tx_id;
+                }
+            }
+            Message::CardanoSigningKeyChanged(path) => {
+                if let ChainConfig::Direct(config) = &mut self.run_options.chain_config {
+                    config.cardano_signing_key = PathBuf::from(path);
+                }
+            }
+            Message::AddCardanoVerificationKey => {
+                if let ChainConfig::Direct(config) = &mut self.run_options.chain_config {
+                    config.cardano_verification_keys.push(PathBuf::new());
+                }
+            }
+            Message::RemoveCardanoVerificationKey(idx) => {
+                if let ChainConfig::Direct(config) = &mut self.run_options.chain_config {
+                    config.cardano_verification_keys.remove(idx);
+                }
+            }
+            Message::MoveCardanoVerificationKeyUp(idx) => {
+                if let ChainConfig::Direct(config) = &mut self.run_options.chain_config {
+                    move_up(&mut config.cardano_verification_keys, idx);
+                }
+            }
+            Message::MoveCardanoVerificationKeyDown(idx) => {
+                if let ChainConfig::Direct(config) = &mut self.run_options.chain_config {
+                    move_down(&mut config.cardano_verification_keys, idx);
+                }
+            }
+            Message::CardanoVerificationKeyChanged(idx, path) => {
+                if let ChainConfig::Direct(config) = &mut self.run_options.chain_config {
+                    if let Some(key) = config.cardano_verification_keys.get_mut(idx) {
+                        *key = PathBuf::from(path);
+                    }
+                }
+            }
+            Message::StartChainFromChanged(point) => {
+                if let ChainConfig::Direct(config) = &mut self.run_options.chain_config {
+                    config.start_chain_from = (!point.is_empty()).then_some(point);
+                }
+            }
+            Message::ContestationPeriodChanged(period) => {
+                if let ChainConfig::Direct(config) = &mut self.run_options.chain_config {
+                    if let Ok(period) = period.parse() {
+                        config.contestation_period = period;
+                    }
+                }
+            }
+            Message::DepositDeadlineChanged(deadline) => {
+                if let ChainConfig::Direct(config) = &mut self.run_options.chain_config {
+                    if let Ok(deadline) = deadline.parse() {
+                        config.deposit_deadline = deadline;
+                    }
+                }
+            }
+            Message::LedgerGenesisChanged(path) => {
+                if let ChainConfig::Offline(config) = &mut self.run_options.chain_config {
+                    config.ledger_genesis_file = (!path.is_empty()).then(|| PathBuf::from(path));
+                }
+            }
+            Message::FindFreePort(host, on_change) => {
+                return Task::perform(async move { free_port(host) }, move |result| match result {
+                    Ok(port) => on_change(port.to_string()),
+                    Err(e) => Message::FreePortFailed(e),
+                });
+            }
+            Message::FreePortFailed(e) => {
+                self.status = Some(e);
+            }
+            Message::AbortHead => {
+                self.confirm_abort = true;
+            }
+            Message::CancelAbortHead => {
+                self.confirm_abort = false;
+            }
+            Message::ConfirmAbortHead => {
+                self.confirm_abort = false;
+                self.status = Some("Sending Abort...".to_string());
+                return Task::perform(
+                    send_abort(
+                        self.run_options.api_host,
+                        self.run_options.api_port,
+                        self.run_options.tls_cert_path.is_some(),
+                    ),
+                    Message::AbortHeadResult,
+                );
+            }
+            Message::AbortHeadResult(result) => {
+                self.status = Some(match result {
+                    Ok(response) => format!("Node answered: {}", response),
+                    Err(e) => e,
+                });
+            }
+            Message::TestApiConnection => {
+                self.api_probe = Some(None);
+                return Task::perform(
+                    probe_api(
+                        self.run_options.api_host,
+                        self.run_options.api_port,
+                        self.run_options.tls_cert_path.is_some(),
+                    ),
+                    Message::ApiConnectionResult,
+                );
+            }
+            Message::ApiConnectionResult(result) => {
+                self.api_probe = Some(Some(result));
+            }
+            Message::MetricsToggled => {
+                self.show_metrics = !self.show_metrics;
+                self.metrics = None;
+                if self.show_metrics {
+                    return self.update(Message::RefreshMetrics(Instant::now()));
+                }
+            }
+            Message::RefreshMetrics(_) => {
+                if let Some(port) = self.run_options.monitoring_port {
+                    return Task::perform(
+                        scrape_metrics(self.run_options.host, port),
+                        Message::MetricsScraped,
+                    );
+                }
+            }
+            Message::MetricsScraped(result) => {
+                if self.show_metrics {
+                    self.metrics = Some(result);
+                }
+            }
+            Message::HydraParamsToggled => {
+                self.hydra_params_expanded = !self.hydra_params_expanded;
+            }
+            Message::MinimumContestationPeriodChanged(value) => {
+                self.run_options
+                    .hydra_protocol_params
+                    .minimum_contestation_period = value.parse().ok();
+            }
+            Message::MaximumTxValidityRangeChanged(value) => {
+                self.run_options
+                    .hydra_protocol_params
+                    .maximum_tx_validity_range = value.parse().ok();
+            }
+            Message::ResetHydraParamsToMainnet => {
+                self.run_options.hydra_protocol_params = HydraProtocolParams::mainnet();
+            }
+            Message::ProtocolParametersChanged(path) => {
+                self.run_options
+                    .ledger_config
+                    .cardano_ledger_protocol_parameters_file = PathBuf::from(path);
+                self.debounce(DebouncedField::ProtocolParameters);
+            }
+            Message::PersistenceDirChanged(dir) => {
+                self.run_options.persistence_dir = PathBuf::from(dir);
+                self.debounce(DebouncedField::PersistenceDir);
+            }
+
+            Message::BrowseTlsCert => {
+                if let Some(path) = self.browse_file(DialogPurpose::Keys) {
+                    return self.handle(Message::TlsCertPathChanged(path));
+                }
+            }
+            Message::BrowseTlsKey => {
+                if let Some(path) = self.browse_file(DialogPurpose::Keys) {
+                    return self.handle(Message::TlsKeyPathChanged(path));
+                }
+            }
+            Message::BrowseHydraSigningKey => {
+                if let Some(path) = self.browse_file(DialogPurpose::Keys) {
+                    return self.handle(Message::HydraSigningKeyChanged(path));
+                }
+            }
+            Message::BrowseHydraVerificationKey(idx) => {
+                if let Some(path) = self.browse_file(DialogPurpose::Keys) {
+                    return self.handle(Message::HydraVerificationKeyChanged(idx, path));
+                }
+            }
+            Message::BrowsePersistenceDir => {
+                let dialog = self.app_config.file_dialog(DialogPurpose::Persistence);
+                if let Some(dir) = dialog.pick_folder() {
+                    self.app_config
+                        .remember_dialog_dir(DialogPurpose::Persistence, &dir);
+                    return self.handle(Message::PersistenceDirChanged(
+                        dir.to_string_lossy().into_owned(),
+                    ));
+                }
+            }
+            Message::LogToFileToggled(log_to_file) => {
+                self.log_to_file = log_to_file;
+            }
+            Message::LogFileChanged(path) => {
+                self.log_file = path;
+            }
+            Message::NodeLogLine(line) => {
+                if self.node_logs.len() == MAX_LOG_LINES {
+                    self.node_logs.pop_front();
+                }
+                self.node_logs.push_back(LogLine::parse(line));
+            }
+            Message::LogLevelSelected(level) => {
+                self.log_level = level;
+            }
+            Message::LogFilterChanged(filter) => {
+                self.log_filter = filter;
+            }
+            Message::ClearLogs => {
+                // Only the panel is cleared; the log file keeps its history.
+                self.node_logs.clear();
+                return scrollable::snap_to(
+                    NODE_LOGS_SCROLLABLE.clone(),
+                    scrollable::RelativeOffset::START,
+                );
+            }
+            Message::RemovePeer(idx) => {
+                self.run_options.peers.remove(idx);
+            }
+            Message::MovePeerUp(idx) => {
+                move_up(&mut self.run_options.peers, idx);
+            }
+            Message::MovePeerDown(idx) => {
+                move_down(&mut self.run_options.peers, idx);
+            }
+            Message::ImportPeers => {
+                let Some(path) = self
+                    .app_config
+                    .file_dialog(DialogPurpose::Config)
+                    .add_filter("JSON", &["json"])
+                    .pick_file()
+                else {
+                    return Task::none();
+                };
+                self.app_config
+                    .remember_dialog_dir(DialogPurpose::Config, &path);
+
+                match RunOptions::load(&path) {
+                    Ok(other) => {
+                        let peers = self.run_options.importable_peers(&other);
+                        self.pending_peers = Some(PendingPeers { path, peers });
+                    }
+                    Err(e) => {
+                        self.status = Some(format!("Failed to load {}: {}", path.display(), e));
+                    }
+                }
+            }
+            Message::ImportPeersFromFile => {
+                let Some(path) = self
+                    .app_config
+                    .file_dialog(DialogPurpose::Config)
+                    .add_filter("Text", &["txt"])
+                    .pick_file()
+                else {
+                    return Task::none();
+                };
+                self.app_config
+                    .remember_dialog_dir(DialogPurpose::Config, &path);
+
+                let contents = match std::fs::read_to_string(&path) {
+                    Ok(contents) => contents,
+                    Err(e) => {
+                        self.status = Some(format!("Failed to read {}: {}", path.display(), e));
+                        return Task::none();
+                    }
+                };
+
+                let (peers, invalid) = parse_peer_list(&contents);
+                let mut added = 0;
+                for peer in peers {
+                    if !self.run_options.peers.contains(&peer) {
+                        self.run_options.peers.push(peer);
+                        added += 1;
+                    }
+                }
+
+                let mut status = format!("Added {} peer(s) from {}", added, path.display());
+                for (line, e) in invalid {
+                    status.push_str(&format!("\nLine {}: {}", line, e));
+                }
+                self.status = Some(status);
+            }
+            Message::ConfirmImportPeers => {
+                if let Some(pending) = self.pending_peers.take() {
+                    self.status = Some(format!(
+                        "Added {} peer(s) from {}",
+                        pending.peers.len(),
+                        pending.path.display()
+                    ));
+                    self.run_options.peers.extend(pending.peers);
+                }
+            }
+            Message::CancelImportPeers => {
+                self.pending_peers = None;
+            }
+            Message::AddEnvVar => {
+                self.extra_env.push((String::new(), String::new()));
+            }
+            Message::RemoveEnvVar(idx) => {
+                self.extra_env.remove(idx);
+            }
+            Message::EnvVarChanged(idx, field, value) => {
+                if let Some((key, val)) = self.extra_env.get_mut(idx) {
+                    match field {
+                        EnvField::Key => *key = value,
+                        EnvField::Value => *val = value,
+                    }
+                }
+            }
+            Message::ExtraArgsChanged(value) => {
+                match split_args(&value) {
+                    Ok(args) => {
+                        self.run_options.extra_args = args;
+                        self.extra_args_error = None;
+                    }
+                    Err(e) => self.extra_args_error = Some(e),
+                }
+                self.extra_args_text = value;
+            }
+            Message::LaunchNode => {
+                let mut addresses = vec![
+                    (self.run_options.host, self.run_options.port),
+                    (self.run_options.api_host, self.run_options.api_port),
+                ];
+                if let Some(port) = self.run_options.monitoring_port {
+                    addresses.push((self.run_options.host, port));
+                }
+
+                self.port_conflicts.clear();
+                return Task::perform(occupied_ports(addresses), Message::LaunchPortsChecked);
+            }
+            Message::LaunchPortsChecked(conflicts) => {
+                if conflicts.is_empty() {
+                    return self.update(Message::LaunchNodeAnyway);
+                }
+                tracing::warn!(?conflicts, "ports already in use before launch");
+                self.port_conflicts = conflicts;
+            }
+            Message::CancelLaunch => {
+                self.port_conflicts.clear();
+            }
+            Message::LaunchNodeAnyway => {
+                self.port_conflicts.clear();
+                self.restart_count = 0;
+                return self.spawn_node();
+            }
+            Message::StopNode => {
+                if self.node_process.is_some() {
+                    self.confirm_stop = true;
+                } else {
+                    // Only a restart is pending; cancelling it needs no confirmation.
+                    self.restart_pending = false;
+                    self.status = Some("hydra-node stopped".to_string());
+                }
+            }
+            Message::CancelStopNode => {
+                self.confirm_stop = false;
+            }
+            Message::ConfirmStopNode => {
+                self.confirm_stop = false;
+                self.restart_pending = false;
+
+                if let Some(child) = &mut self.node_process {
+                    tracing::info!(pid = child.id(), "stopping hydra-node");
+
+                    match request_shutdown(child) {
+                        Ok(()) => {
+                            self.stopping_since = Some(Instant::now());
+                            self.status = Some("Stopping hydra-node...".to_string());
+                        }
+                        Err(e) => {
+                            tracing::warn!(error = %e, "failed to signal hydra-node, killing it");
+                            let _ = child.kill();
+                            let _ = child.wait();
+                            self.node_process = None;
+                            self.status = Some(format!("hydra-node killed ({})", e));
+                        }
+                    }
+                }
+            }
+            Message::AutoRestartToggled(auto_restart) => {
+                self.auto_restart = auto_restart;
+            }
+            Message::NodeProcessTick(_) => {
+                let Some(child) = &mut self.node_process else {
+                    return Task::none();
+                };
+
+                let exit_status = match child.try_wait() {
+                    Ok(Some(exit_status)) => exit_status,
+                    Ok(None) => {
+                        let timed_out = self
+                            .stopping_since
+                            .is_some_and(|since| since.elapsed() >= SHUTDOWN_TIMEOUT);
+
+                        if timed_out {
+                            tracing::warn!(pid = child.id(), "hydra-node did not stop, killing it");
+                            let _ = child.kill();
+                            let _ = child.wait();
+                            self.node_process = None;
+                            self.stopping_since = None;
+                            self.status = Some(format!(
+                                "hydra-node did not stop within {} seconds and was killed",
+                                SHUTDOWN_TIMEOUT.as_secs()
+                            ));
+                        }
+                        return Task::none();
+                    }
+                    Err(e) => {
+                        tracing::warn!(error = %e, "failed to poll hydra-node");
+                        return Task::none();
+                    }
+                };
+
+                self.node_process = None;
+                tracing::info!(%exit_status, "hydra-node exited");
+
+                if self.stopping_since.take().is_some() {
+                    self.status = Some(if GRACEFUL_SHUTDOWN {
+                        format!("hydra-node shut down gracefully ({})", exit_status)
+                    } else {
+                        format!("hydra-node was killed ({})", exit_status)
+                    });
+                } else if exit_status.success() || !self.auto_restart {
+                    self.status = Some(format!("hydra-node exited ({})", exit_status));
+                } else if self.restart_count >= MAX_RESTARTS {
+                    self.status = Some(format!(
+                        "hydra-node exited ({}); gave up after {} restarts",
+                        exit_status, MAX_RESTARTS
+                    ));
+                } else {
+                    let backoff = Duration::from_secs(1 << self.restart_count.min(6))
+                        .min(MAX_RESTART_BACKOFF);
+                    self.status = Some(format!(
+                        "hydra-node exited ({}); restarting in {}s",
+                        exit_status,
+                        backoff.as_secs()
+                    ));
+                    self.restart_pending = true;
+
+                    return Task::perform(tokio::time::sleep(backoff), |_| Message::RestartNode);
+                }
+            }
+            Message::RestartNode => {
+                // A stop in the meantime cancels the restart.
+                if self.restart_pending {
+                    self.restart_pending = false;
+                    self.restart_count += 1;
+                    return self.spawn_node();
+                }
+            }
+            Message::SaveSettings => {
+                let _span = tracing::info_span!("save_settings").entered();
+                tracing::debug!(run_options = ?self.run_options, "saving settings");
+
+                if let Err(e) = self.check_chain_config_type() {
+                    tracing::warn!(error = %e, "refusing to save settings");
+                    self.status = Some(e);
+                    return Task::none();
+                }
+
+                let initial_utxo = match self.initial_utxo() {
+                    Ok(initial_utxo) => initial_utxo,
+                    Err(e) => {
+                        tracing::warn!(error = %e, "refusing to save settings");
+                        self.status = Some(e);
+                        return Task::none();
+                    }
+                };
+
+                let path = match &self.config_path {
+                    Some(path) => path.clone(),
+                    None => {
+                        let Some(path) = self
+                            .app_config
+                            .file_dialog(DialogPurpose::Config)
+                            .add_filter("JSON", &["json"])
+                            .set_file_name("juno-config.json")
+                            .save_file()
+                        else {
+                            return Task::none();
+                        };
+                        self.app_config
+                            .remember_dialog_dir(DialogPurpose::Config, &path);
+                        path
+                    }
+                };
+
+                if let Err(e) = write_initial_utxo(initial_utxo) {
+                    tracing::error!(error = %e, "failed to write initial UTxO");
+                    self.status = Some(e);
+                    return Task::none();
+                }
+
+                match self.run_options.save(&path) {
+                    Ok(()) => {
+                        tracing::info!(path = %path.display(), "saved settings");
+                        self.status = Some(format!("Saved settings to {}", path.display()));
+                        self.saved_options = self.run_options.clone();
+                        self.config_path = Some(path);
+                        self.refresh_derived();
+                    }
+                    Err(e) => {
+                        tracing::error!(path = %path.display(), error = %e, "failed to save settings");
+                        self.status = Some(format!("Failed to save {}: {}", path.display(), e));
+                    }
+                }
+            }
+            Message::ExportBundle => {
+                let _span = tracing::info_span!("export_bundle").entered();
+
+                let initial_utxo = match self.initial_utxo() {
+                    Ok(initial_utxo) => initial_utxo,
+                    Err(e) => {
+                        tracing::warn!(error = %e, "refusing to export bundle");
+                        self.status = Some(e);
+                        return Task::none();
+                    }
+                };
+
+                let Some(path) = self
+                    .app_config
+                    .file_dialog(DialogPurpose::Config)
+                    .add_filter("Zip", &["zip"])
+                    .set_file_name("juno-bundle.zip")
+                    .save_file()
+                else {
+                    return Task::none();
+                };
+                self.app_config
+                    .remember_dialog_dir(DialogPurpose::Config, &path);
+
+                if let Err(e) = write_initial_utxo(initial_utxo) {
+                    tracing::error!(error = %e, "failed to write initial UTxO");
+                    self.status = Some(e);
+                    return Task::none();
+                }
+
+                self.status = Some(match export_bundle(&self.run_options, &path) {
+                    Ok(warnings) if warnings.is_empty() => {
+                        format!("Exported bundle to {}", path.display())
+                    }
+                    Ok(warnings) => format!(
+                        "Exported bundle to {} ({})",
+                        path.display(),
+                        warnings.join("; ")
+                    ),
+                    Err(e) => {
+                        tracing::error!(
+                            path = %path.display(),
+                            error = %e,
+                            "failed to export bundle"
+                        );
+                        format!("Failed to export bundle to {}: {}", path.display(), e)
+                    }
+                });
+            }
+            Message::ExportArgs => {
+                let Some(path) = self
+                    .app_config
+                    .file_dialog(DialogPurpose::Config)
+                    .add_filter("Text", &["txt"])
+                    .set_file_name("args.txt")
+                    .save_file()
+                else {
+                    return Task::none();
+                };
+                self.app_config
+                    .remember_dialog_dir(DialogPurpose::Config, &path);
+
+                self.status = Some(
+                    match std::fs::write(&path, self.run_options.to_args_lines()) {
+                        Ok(()) => format!("Exported arguments to {}", path.display()),
+                        Err(e) => format!("Failed to write {}: {}", path.display(), e),
+                    },
+                );
+            }
+            Message::CopyArgsJson => match self.run_options.to_args_json() {
+                Ok(json) => {
+                    self.status = Some("Copied arguments as a JSON array".to_string());
+                    return iced::clipboard::write(json);
+                }
+                Err(e) => self.status = Some(format!("Failed to serialize arguments: {}", e)),
+            },
+            Message::CopyValue(value) => {
+                return iced::clipboard::write(value);
+            }
+            Message::ImportBundle => {
+                let _span = tracing::info_span!("import_bundle").entered();
+
+                let Some(archive) = self
+                    .app_config
+                    .file_dialog(DialogPurpose::Config)
+                    .add_filter("Zip", &["zip"])
+                    .pick_file()
+                else {
+                    return Task::none();
+                };
+                self.app_config
+                    .remember_dialog_dir(DialogPurpose::Config, &archive);
+                let Some(target) = self
+                    .app_config
+                    .file_dialog(DialogPurpose::Config)
+                    .set_title("Extract bundle to")
+                    .pick_folder()
+                else {
+                    return Task::none();
+                };
+                self.app_config
+                    .remember_dialog_dir(DialogPurpose::Config, &target);
+
+                match import_bundle(&archive, &target) {
+                    Ok((path, run_options)) => self.offer_load(path, run_options),
+                    Err(e) => {
+                        tracing::error!(
+                            bundle = %archive.display(),
+                            error = %e,
+                            "failed to import bundle"
+                        );
+                        self.status =
+                            Some(format!("Failed to import {}: {}", archive.display(), e));
+                    }
+                }
+            }
+            Message::LoadSettings | Message::ImportConfig => {
+                let Some(path) = self
+                    .app_config
+                    .file_dialog(DialogPurpose::Config)
+                    .add_filter("JSON", &["json"])
+                    .pick_file()
+                else {
+                    return Task::none();
+                };
+                self.app_config
+                    .remember_dialog_dir(DialogPurpose::Config, &path);
+                self.open_config(path);
+            }
+            Message::ConfirmLoad => {
+                if let Some(pending) = self.pending_load.take() {
+                    self.apply_loaded(pending.path, pending.run_options);
+                }
+            }
+            Message::CancelLoad => {
+                self.pending_load = None;
+            }
+            Message::WatchConfigToggled(watch) => {
+                self.watch_config = watch;
+            }
+            Message::ConfigFileChanged(Ok(())) => {
+                self.debounce(DebouncedField::ConfigFile);
+            }
+            Message::ConfigFileChanged(Err(e)) => {
+                tracing::warn!(error = %e, "config watcher failed");
+                self.status = Some(format!("Stopped watching the config file: {}", e));
+                self.watch_config = false;
+            }
+            Message::LoadExample => {
+                self.offer_changes(&example_run_options(), DiffSource::Template);
+            }
+            Message::StartFromScratch => {
+                self.start_from(RunOptions::default());
+            }
+            Message::UseTemplate(template) => {
+                self.offer_changes(&template.run_options(), DiffSource::Template);
+            }
+            Message::ToggleDiffSelection(idx) => {
+                if let Some(modal) = &mut self.diff_modal {
+                    modal.toggle(idx);
+                }
+            }
+            Message::ApplySelectedDiffs => {
+                if let Some(modal) = self.diff_modal.take() {
+                    let mut options = modal.apply_selected(&self.run_options);
+                    match modal.source {
+                        // Taking every field of a profile switches to it.
+                        DiffSource::Profile(node_id) if modal.all_selected() => {
+                            match self.switch_profile(options) {
+                                Ok(()) => {
+                                    self.profiles.remove(&node_id);
+                                    self.save_profiles();
+                                }
+                                Err(e) => self.status = Some(e),
+                            }
+                        }
+                        // Otherwise the selected fields are copied into the
+                        // current profile and the other one is kept as it is,
+                        // so it keeps its node ID.
+                        DiffSource::Profile(node_id) => {
+                            options.node_id = self.run_options.node_id.clone();
+                            self.start_from(options);
+                            self.status = Some(format!(
+                                "Copied the selected fields from profile {}",
+                                node_id.0
+                            ));
+                        }
+                        DiffSource::Template => self.start_from(options),
+                    }
+                }
+            }
+            Message::CloseDiffModal => {
+                self.diff_modal = None;
+            }
+            Message::LoadFromEnv => match RunOptions::from_env() {
+                Ok(run_options) => {
+                    self.run_options = run_options;
+                    self.chain_config_type = ChainConfigType::of(&self.run_options.chain_config);
+                    self.sync_extra_args_text();
+                    self.status = Some("Loaded settings from the environment".to_string());
+                }
+                Err(e) => {
+                    self.status = Some(format!(
+                        "Failed to load settings from the environment: {}",
+                        e
+                    ));
+                }
+            },
+            Message::InstanceOffsetChanged(offset) => {
+                self.instance_offset = offset;
+            }
+            Message::InstanceSuffixChanged(suffix) => {
+                self.instance_suffix = suffix;
+            }
+            Message::ProfileSelected(node_id) => {
+                if let Some(profile) = self.profiles.get(&node_id).cloned() {
+                    self.offer_changes(&profile, DiffSource::Profile(node_id));
+                }
+            }
+            Message::DuplicateProfile => {
+                let mut profile = self.run_options.clone();
+                if self.increment_on_duplicate {
+                    profile = profile.next_node();
+                    while self.profiles.contains_key(&profile.node_id) {
+                        profile = profile.next_node();
+                    }
+                } else {
+                    while profile.node_id == self.run_options.node_id
+                        || self.profiles.contains_key(&profile.node_id)
+                    {
+                        profile.node_id = NodeId(format!("{} (copy)", profile.node_id.0));
+                    }
+                }
+
+                let created = format!("Created profile {}", profile.node_id.0);
+                self.status = Some(match self.switch_profile(profile) {
+                    Ok(()) => created,
+                    Err(e) => e,
+                });
+            }
+            Message::IncrementOnDuplicateToggled(increment) => {
+                self.increment_on_duplicate = increment;
+            }
+            Message::CreateNodeInstance => {
+                let instance = self
+                    .instance_offset
+                    .parse()
+                    .map_err(|_| format!("Invalid port offset: {}", self.instance_offset))
+                    .and_then(|offset| {
+                        self.run_options
+                            .derive_instance(offset, &self.instance_suffix)
+                    });
+
+                let instance = match instance {
+                    Ok(instance) => instance,
+                    Err(e) => {
+                        self.status = Some(e);
+                        return Task::none();
+                    }
+                };
+
+                let Some(path) = self
+                    .app_config
+                    .file_dialog(DialogPurpose::Config)
+                    .add_filter("JSON", &["json"])
+                    .set_file_name(format!("{}.json", instance.node_id.0))
+                    .save_file()
+                else {
+                    return Task::none();
+                };
+                self.app_config
+                    .remember_dialog_dir(DialogPurpose::Config, &path);
+
+                self.status = Some(match instance.save(&path) {
+                    Ok(()) => format!("Saved {} to {}", instance.node_id.0, path.display()),
+                    Err(e) => format!("Failed to save {}: {}", path.display(), e),
+                });
+            }
+            Message::InitialUtxoChanged(path) => {
+                if let ChainConfig::Offline(config) = &mut self.run_options.chain_config {
+                    config.initial_utxo_file = PathBuf::from(path);
+                    self.utxo_file_validation = None;
+                    self.debounce(DebouncedField::InitialUtxoFile);
+                }
+            }
+            Message::ValidateUtxoFile => {
+                if let ChainConfig::Offline(config) = &self.run_options.chain_config {
+                    self.utxo_file_progress = Some(0);
+                    self.utxo_file_validation = None;
+                    return Task::run(
+                        validate_file(config.initial_utxo_file.clone()),
+                        Message::UtxoFileValidation,
+                    );
+                }
+            }
+            Message::NormalizeUtxoFile => {
+                if let ChainConfig::Offline(config) = &self.run_options.chain_config {
+                    let path = &config.initial_utxo_file;
+                    self.status = Some(match normalize_file(path) {
+                        Ok(()) => format!("Normalized {}", path.display()),
+                        Err(e) => format!("Failed to normalize {}: {}", path.display(), e),
+                    });
+                }
+            }
+            Message::UtxoFileValidation(event) => match event {
+                UtxoValidationEvent::Progress(count) => self.utxo_file_progress = Some(count),
+                UtxoValidationEvent::Complete(result) => {
+                    self.utxo_file_progress = None;
+                    self.utxo_file_validation = Some(result);
+                }
+            },
+            Message::UtxoTextChanged(action) => {
+                let is_edit = action.is_edit();
+                self.utxo_text.perform(action);
+
+                if is_edit {
+                    self.utxo_validation = None;
+                    self.debounce(DebouncedField::UtxoText);
+                }
+            }
+            Message::GenerateSampleUtxo => match serde_json::to_string_pretty(&sample_utxo()) {
+                Ok(json) => {
+                    self.utxo_text = text_editor::Content::with_text(&json);
+                    self.utxo_validation = None;
+                    self.debounce(DebouncedField::UtxoText);
+                }
+                Err(e) => self.status = Some(format!("Failed to generate a sample UTxO: {}", e)),
+            },
+            Message::LoadUtxoEntries => {
+                if let ChainConfig::Offline(config) = &self.run_options.chain_config {
+                    let entries = std::fs::read_to_string(&config.initial_utxo_file)
+                        .map_err(|e| e.to_string())
+                        .and_then(|json| parse_entries(&json).map_err(|e| e.to_string()));
+
+                    match entries {
+                        Ok(entries) => {
+                            self.utxo_entries = entries;
+                            self.utxo_ada_input = None;
+                        }
+                        Err(e) => {
+                            self.status = Some(format!(
+                                "Failed to load {}: {}",
+                                config.initial_utxo_file.display(),
+                                e
+                            ));
+                        }
+                    }
+                }
+            }
+            Message::AddUtxoEntry => {
+                self.utxo_entries
+                    .push((String::new(), TxOut::new(String::new(), 0)));
+            }
+            Message::RemoveUtxoEntry(idx) => {
+                self.utxo_entries.remove(idx);
+                self.utxo_ada_input = None;
+            }
+            Message::UtxoFieldChanged(idx, field, value) => {
+                if let Some((reference, tx_out)) = self.utxo_entries.get_mut(idx) {
+                    match field {
+                        UtxoField::Reference => *reference = value,
+                        UtxoField::Address => tx_out.address = value,
+                        UtxoField::Lovelace => {
+                            if let Ok(lovelace) = value.parse() {
+                                tx_out.value.lovelace = lovelace;
+                                self.utxo_ada_input = None;
+                            }
+                        }
+                        UtxoField::Ada => {
+                            match value.trim().parse::<f64>() {
+                                Ok(ada) if ada.is_finite() && ada >= 0.0 => {
+                                    tx_out.value.lovelace = ada_to_lovelace(ada);
+                                }
+                                _ if value.trim().is_empty() => tx_out.value.lovelace = 0,
+                                _ => {}
+                            }
+                            self.utxo_ada_input = Some((idx, value));
+                        }
+                    }
+                }
+            }
+            Message::PreviewDatum(idx) => {
+                self.datum_preview = self.utxo_entries.get(idx).and_then(|(utxo_ref, tx_out)| {
+                    Some(DatumPreview {
+                        utxo_ref: utxo_ref.clone(),
+                        lines: highlight(&format_plutus_data(tx_out.inline_datum()?)),
+                    })
+                });
+            }
+            Message::CloseDatumPreview => {
+                self.datum_preview = None;
+            }
+            Message::AddAsset(idx) => {
+                if let Some((_, tx_out)) = self.utxo_entries.get_mut(idx) {
+                    let mut assets = tx_out.value.asset_list();
+                    if !assets
+                        .iter()
+                        .any(|(policy_id, name, _)| policy_id.is_empty() && name.is_empty())
+                    {
+                        assets.push((String::new(), String::new(), 0));
+                    }
+                    tx_out.value.set_asset_list(assets);
+                }
+            }
+            Message::RemoveAsset(idx, policy_id, name) => {
+                if let Some((_, tx_out)) = self.utxo_entries.get_mut(idx) {
+                    if let Some(names) = tx_out.value.assets.get_mut(&policy_id) {
+                        names.remove(&name);
+                        if names.is_empty() {
+                            tx_out.value.assets.remove(&policy_id);
+                        }
+                    }
+                }
+            }
+            Message::AssetChanged(idx, asset_idx, field, value) => {
+                if let Some((_, tx_out)) = self.utxo_entries.get_mut(idx) {
+                    let mut assets = tx_out.value.asset_list();
+                    if let Some((policy_id, name, quantity)) = assets.get_mut(asset_idx) {
+                        match field {
+                            AssetField::PolicyId => *policy_id = value,
+                            AssetField::AssetName => *name = value,
+                            AssetField::Quantity => {
+                                if let Ok(parsed) = value.parse() {
+                                    *quantity = parsed;
+                                }
+                            }
+                        }
+                    }
+                    tx_out.value.set_asset_list(assets);
+                }
+            }
+            Message::BrowseInitialUtxo => {
+                if let Some(path) = self.browse_file(DialogPurpose::Utxo) {
+                    return self.handle(Message::InitialUtxoChanged(path));
+                }
+            }
+            Message::HydraNodeBinaryChanged(binary) => {
+                self.hydra_node_binary = binary;
+                self.hydra_node_version = None;
+                self.help_text = None;
+                self.refresh_derived();
+                self.debounce(DebouncedField::HydraNodeBinary);
+            }
+            Message::BrowseHydraNodeBinary => {
+                if let Some(path) = self.browse_file(DialogPurpose::Binary) {
+                    return self
+                        .update(Message::HydraNodeBinaryChanged(path))
+                        .chain(Task::done(Message::CheckHydraNodeBinary));
+                }
+            }
+            Message::DetectHydraNodeBinary => match find_on_path(HYDRA_NODE_BINARY) {
+                Some(path) => {
+                    self.status = Some(format!("Found {}", path.display()));
+                    return self
+                        .update(Message::HydraNodeBinaryChanged(
+                            path.to_string_lossy().into_owned(),
+                        ))
+                        .chain(Task::done(Message::CheckHydraNodeBinary));
+                }
+                None => {
+                    self.status = Some(format!("{} was not found on PATH", HYDRA_NODE_BINARY));
+                }
+            },
+            Message::DismissChangelog => {
+                self.changelog = None;
+                self.app_config.last_seen_version = Some(env!("CARGO_PKG_VERSION").to_string());
+
+                if let Err(e) = self.app_config.save() {
+                    tracing::warn!(error = %e, "failed to save app state");
+                }
+            }
+            Message::CheckHydraNodeBinary => {
+                self.checking_version = true;
+                let binary = self.hydra_node_binary.clone();
+                return Task::perform(
+                    async move { hydra_node_version(&binary) },
+                    Message::HydraNodeBinaryResult,
+                );
+            }
+            Message::LoadHydraHelp => {
+                self.help_expanded = !self.help_expanded;
+
+                if self.help_expanded && self.help_text.is_none() {
+                    let binary = self.hydra_node_binary.clone();
+                    return Task::perform(
+                        async move { hydra_node_help(&binary) },
+                        Message::HydraHelpLoaded,
+                    );
+                }
+            }
+            Message::HydraHelpLoaded(Ok(help)) => {
+                self.help_text = Some(help);
+            }
+            Message::HydraHelpLoaded(Err(e)) => {
+                self.help_expanded = false;
+                self.status = Some(e);
+            }
+            Message::HydraNodeBinaryResult(result) => {
+                self.checking_version = false;
+                if let Err(e) = &result {
+                    tracing::warn!(error = %e, "hydra-node binary check failed");
+                }
+                self.hydra_node_version = Some(result);
+                self.refresh_derived();
+            }
+            Message::DownloadHydraNode => {
+                self.download_progress = Some(0.0);
+            }
+            Message::DownloadProgress(progress) => {
+                self.download_progress = Some(progress);
+            }
+            Message::DownloadComplete(result) => {
+                self.download_progress = None;
+                match result {
+                    Ok(path) => {
+                        self.status = Some(format!("Installed hydra-node to {}", path.display()));
+                        return self
+                            .update(Message::HydraNodeBinaryChanged(
+                                path.to_string_lossy().into_owned(),
+                            ))
+                            .chain(Task::done(Message::CheckHydraNodeBinary));
+                    }
+                    Err(e) => {
+                        self.status = Some(format!("Failed to download hydra-node: {}", e));
+                    }
+                }
+            }
+            Message::PreviewKey(path) => {
+                let preview = KeyPreview::load(&path);
+                self.key_preview = Some((path, preview));
+            }
+            Message::ValidateKeyPair => {
+                if let Some(vk_path) = self.run_options.hydra_verification_keys.first() {
+                    self.key_pair_validation = Some(
+                        validate_key_pair(&self.run_options.hydra_signing_key, vk_path)
+                            .map_err(|e| e.to_string()),
+                    );
+                }
+            }
+            Message::CloseKeyPreview => {
+                self.key_preview = None;
+            }
+            Message::FixKeyPermissions(path) => {
+                if let Err(e) = restrict_permissions(&path) {
+                    self.status = Some(format!(
+                        "Failed to change permissions of {}: {}",
+                        path.display(),
+                        e
+                    ));
+                }
+                self.check_key_permissions();
+            }
+            Message::ResetField(field) => {
+                reset_field(&mut self.run_options, field);
+                self.chain_config_type = ChainConfigType::of(&self.run_options.chain_config);
+            }
+            Message::DebounceTick(now) => {
+                let settled: Vec<DebouncedField> = self
+                    .pending_inputs
+                    .iter()
+                    .filter(|(_, edited_at)| now.duration_since(**edited_at) >= INPUT_DEBOUNCE)
+                    .map(|(field, _)| *field)
+                    .collect();
+
+                for field in &settled {
+                    self.pending_inputs.remove(field);
+                }
+
+                return Task::batch(
+                    settled
+                        .into_iter()
+                        .map(|field| Task::done(Message::InputSettled(field))),
+                );
+            }
+            Message::InputSettled(field) => match field {
+                DebouncedField::UtxoText => {
+                    self.utxo_validation = self
+                        .pasted_utxo()
+                        .map(|json| validate_json(json).map_err(|e| e.to_string()));
+                }
+                DebouncedField::ConfigFile => self.reload_changed_config(),
+                DebouncedField::HydraNodeBinary => {
+                    self.app_config.hydra_node_binary = Some(self.hydra_node_binary.clone());
+                    if let Err(e) = self.app_config.save() {
+                        tracing::warn!(error = %e, "failed to save app state");
+                    }
+                }
+                DebouncedField::TlsCertPath => {
+                    self.check_tls_cert();
+                    self.validate_options();
+                }
+                DebouncedField::HydraSigningKey => {
+                    self.check_key_permissions();
+                    self.validate_options();
+                }
+                // Validation parses the TLS key, looks for duplicate
+                // verification keys and compares the persistence directory
+                // with the other profiles.
+                DebouncedField::TlsKeyPath
+                | DebouncedField::HydraVerificationKey(_)
+                | DebouncedField::PersistenceDir => self.validate_options(),
+                DebouncedField::NodeSocket => {
+                    if let ChainConfig::Direct(config) = &self.run_options.chain_config {
+                        self.node_socket_status = Some(node_socket_status(&config.node_socket));
+                    }
+                }
+                DebouncedField::InitialUtxoFile => return self.handle(Message::ValidateUtxoFile),
+                DebouncedField::ProtocolParameters => self.check_protocol_params(),
+                DebouncedField::Options => self.refresh_checks(),
+            },
+        }
+
+        Task::none()
+    }
+
+    pub fn subscription(&self) -> Subscription<Message> {
+        let debounce = if self.pending_inputs.is_empty() {
+            Subscription::none()
+        } else {
+            time::every(Duration::from_millis(50)).map(Message::DebounceTick)
+        };
+
+        let download = if self.download_progress.is_some() {
+            Subscription::run_with_id("hydra-node-download", download_hydra_node()).map(|event| {
+                match event {
+                    DownloadEvent::Progress(progress) => Message::DownloadProgress(progress),
+                    DownloadEvent::Complete(result) => Message::DownloadComplete(result),
+                }
+            })
+        } else {
+            Subscription::none()
+        };
+
+        let config_watch = match &self.config_path {
+            Some(path) if self.watch_config => {
+                Subscription::run_with_id(("config-watch", path.clone()), watch_file(path.clone()))
+                    .map(Message::ConfigFileChanged)
+            }
+            _ => Subscription::none(),
+        };
+
+        let node_socket = match &self.run_options.chain_config {
+            ChainConfig::Direct(config) => time::every(Duration::from_secs(2))
+                .with(config.node_socket.clone())
+                .map(|(socket, _)| Message::NodeSocketStatus(node_socket_status(&socket))),
+            ChainConfig::Offline(_) => Subscription::none(),
+        };
+
+        let metrics = if self.show_metrics && self.run_options.monitoring_port.is_some() {
+            time::every(Duration::from_secs(5)).map(Message::RefreshMetrics)
+        } else {
+            Subscription::none()
+        };
+
+        let node_process = if self.node_process.is_some() {
+            time::every(Duration::from_secs(1)).map(Message::NodeProcessTick)
+        } else {
+            Subscription::none()
+        };
+
+        let window_events = event::listen_with(|event, _status, id| match event {
+            Event::Window(window::Event::Resized(size)) => Some(Message::WindowResized(size)),
+            Event::Window(window::Event::CloseRequested) => Some(Message::WindowCloseRequested(id)),
+            Event::Window(window::Event::FileDropped(path)) => Some(Message::FileDropped(path)),
+            _ => None,
+        });
+
+        let shortcuts = keyboard::on_key_press(|key, modifiers| {
+            if !modifiers.command() {
+                return None;
+            }
+            match key.as_ref() {
+                keyboard::Key::Character("z") if modifiers.shift() => Some(Message::Redo),
+                keyboard::Key::Character("z") => Some(Message::Undo),
+                keyboard::Key::Character("y") => Some(Message::Redo),
+                _ => None,
+            }
+        });
+
+        Subscription::batch([
+            debounce,
+            download,
+            config_watch,
+            node_socket,
+            node_process,
+            metrics,
+            window_events,
+            shortcuts,
+        ])
+    }
+
+    /// The chain type picker is separate UI state from `run_options`, so make
+    /// sure both still agree before anything is written out.
+    fn check_chain_config_type(&self) -> Result<(), String> {
+        let actual = ChainConfigType::of(&self.run_options.chain_config);
+
+        if actual != self.chain_config_type {
+            return Err(format!(
+                "Chain configuration is {} but {} is selected; reselect the configuration type before saving",
+                actual, self.chain_config_type
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// The pasted UTxO JSON, if any was entered.
+    fn pasted_utxo(&self) -> Option<String> {
+        let json = self.utxo_text.text();
+        (!json.trim().is_empty()).then_some(json)
+    }
+
+    /// The edited UTxO entries, or else the pasted UTxO JSON, and the Offline
+    /// chain's initial UTxO file they are written to on save. Invalid JSON is
+    /// an error, so it never replaces a good file.
+    fn initial_utxo(&self) -> Result<Option<(PathBuf, String)>, String> {
+        let ChainConfig::Offline(config) = &self.run_options.chain_config else {
+            return Ok(None);
+        };
+
+        let json = if self.utxo_entries.is_empty() {
+            match self.pasted_utxo() {
+                Some(json) => json,
+                None => return Ok(None),
+            }
+        } else {
+            entries_to_json(&self.utxo_entries).map_err(|e| e.to_string())?
+        };
+
+        validate_json(json.clone()).map_err(|e| {
+            format!(
+                "Not writing invalid initial UTxO to {}: {}",
+                config.initial_utxo_file.display(),
+                e
+            )
+        })?;
+
+        Ok(Some((config.initial_utxo_file.clone(), json)))
+    }
+
+    fn has_unsaved_changes(&self) -> bool {
+        self.run_options != self.saved_options
+    }
+
+    /// Applies loaded options straight away, or asks first when the form has
+    /// unsaved changes.
+    fn offer_load(&mut self, path: PathBuf, run_options: RunOptions) {
+        if self.has_unsaved_changes() {
+            let diff = self.run_options.diff(&run_options);
+            self.pending_load = Some(PendingLoad {
+                path,
+                run_options,
+                diff,
+                external: false,
+            });
+        } else {
+            self.apply_loaded(path, run_options);
+        }
+    }
+
+    /// Puts a dropped file into `field`. The key lists get one entry appended
+    /// per file, so several keys can be dropped at once.
+    fn drop_file(&mut self, field: PathField, path: PathBuf) -> Task<Message> {
+        let text = path.to_string_lossy().into_owned();
+
+        match field {
+            PathField::TlsCert => self.handle(Message::TlsCertPathChanged(text)),
+            PathField::TlsKey => self.handle(Message::TlsKeyPathChanged(text)),
+            PathField::HydraSigningKey => self.handle(Message::HydraSigningKeyChanged(text)),
+            PathField::HydraVerificationKeys => {
+                self.run_options.hydra_verification_keys.push(path);
+                Task::none()
+            }
+            PathField::PersistenceDir => self.handle(Message::PersistenceDirChanged(text)),
+            PathField::NodeSocket => self.handle(Message::NodeSocketChanged(text)),
+            PathField::CardanoSigningKey => self.handle(Message::CardanoSigningKeyChanged(text)),
+            PathField::CardanoVerificationKeys => {
+                if let ChainConfig::Direct(config) = &mut self.run_options.chain_config {
+                    config.cardano_verification_keys.push(path);
+                }
+                Task::none()
+            }
+            PathField::InitialUtxo => self.handle(Message::InitialUtxoChanged(text)),
+            PathField::LedgerGenesis => self.handle(Message::LedgerGenesisChanged(text)),
+            PathField::ProtocolParameters => self.handle(Message::ProtocolParametersChanged(text)),
+        }
+    }
+
+    /// Asks for a file for `purpose` and returns its path as typed text.
+    fn browse_file(&mut self, purpose: DialogPurpose) -> Option<String> {
+        let path = self.app_config.file_dialog(purpose).pick_file()?;
+        self.app_config.remember_dialog_dir(purpose, &path);
+        Some(path.to_string_lossy().into_owned())
+    }
+
+    /// Keeps rejected text in `field` on screen together with the reason.
+    fn reject_input(&mut self, field: FieldId, text: String, reason: String) {
+        self.invalid_inputs.insert(field, (text, reason));
+    }
+
+    /// What the input for `field` shows: the rejected text while there is
+    /// one, `value` otherwise.
+    fn input_text(&self, field: FieldId, value: String) -> String {
+        match self.invalid_inputs.get(&field) {
+            Some((text, _)) => text.clone(),
+            None => value,
+        }
+    }
+
+    fn input_error(&self, field: FieldId) -> Option<Element<Message>> {
+        let (_, reason) = self.invalid_inputs.get(&field)?;
+        Some(text(reason.clone()).style(text::danger).into())
+    }
+
+    /// Loads the config at `path` into the form, reporting failures in the
+    /// status line.
+    pub fn open_config(&mut self, path: PathBuf) {
+        let _span = tracing::info_span!("load_settings").entered();
+
+        match RunOptions::load(&path) {
+            Ok(run_options) => self.offer_load(path, run_options),
+            Err(e) => {
+                tracing::error!(path = %path.display(), error = %e, "failed to load settings");
+                self.status = Some(format!("Failed to load {}: {}", path.display(), e));
+            }
+        }
+    }
+
+    /// Asks which fields to take from `incoming` before applying them.
+    fn offer_changes(&mut self, incoming: &RunOptions, source: DiffSource) {
+        let modal = DiffModal::new(&self.run_options, incoming, source);
+
+        if modal.diffs.is_empty() {
+            self.status = Some("Nothing would change".to_string());
+        } else {
+            self.diff_modal = Some(modal);
+        }
+    }
+
+    /// Replaces the form with `run_options` that do not come from a file.
+    pub fn start_from(&mut self, run_options: RunOptions) {
+        self.run_options = run_options;
+        self.chain_config_type = ChainConfigType::of(&self.run_options.chain_config);
+        self.sync_extra_args_text();
+    }
+
+    fn apply_loaded(&mut self, path: PathBuf, run_options: RunOptions) {
+        tracing::info!(path = %path.display(), "loaded settings");
+        self.chain_config_type = ChainConfigType::of(&run_options.chain_config);
+        self.status = Some(format!("Loaded settings from {}", path.display()));
+        self.saved_options = run_options.clone();
+        self.run_options = run_options;
+        self.config_path = Some(path);
+        self.sync_extra_args_text();
+        self.refresh_derived();
+    }
+
+    /// Makes `profile` the current options and keeps the current ones as a
+    /// profile. Refused while the current node ID was edited to one another
+    /// profile already has, as that profile would be overwritten.
+    fn switch_profile(&mut self, profile: RunOptions) -> Result<(), String> {
+        if self.profiles.contains_key(&self.run_options.node_id) {
+            return Err(format!(
+                "Another profile already has node ID {}; change it before switching profiles",
+                self.run_options.node_id.0
+            ));
+        }
+
+        let previous = std::mem::replace(&mut self.run_options, profile);
+        self.profiles.insert(previous.node_id.clone(), previous);
+        self.chain_config_type = ChainConfigType::of(&self.run_options.chain_config);
+        self.sync_extra_args_text();
+        self.save_profiles();
+        Ok(())
+    }
+
+    fn save_profiles(&self) {
+        if let Err(e) = ProfileStore::save(&self.profiles) {
+            tracing::warn!(error = %e, "failed to save profiles");
+        }
+    }
+
+    fn sync_extra_args_text(&mut self) {
+        self.extra_args_text = join_args(&self.run_options.extra_args);
+        self.extra_args_error = None;
+    }
+
+    /// Offers to reload the watched config, unless what is on disk already
+    /// matches the form (e.g. because Juno just saved it).
+    fn reload_changed_config(&mut self) {
+        let Some(path) = self.config_path.clone() else {
+            return;
+        };
+
+        match RunOptions::load(&path) {
+            Ok(run_options) if run_options == self.run_options => {}
+            Ok(run_options) => {
+                tracing::info!(path = %path.display(), "config changed on disk");
+                let diff = self.run_options.diff(&run_options);
+                self.pending_load = Some(PendingLoad {
+                    path,
+                    run_options,
+                    diff,
+                    external: true,
+                });
             }
+            // Editors may leave the file half written; the next write retries.
+            Err(e) => tracing::debug!(path = %path.display(), error = %e, "config not readable"),
+        }
+    }
+
+    /// Re-runs the checks that read files for the current options. `view`
+    /// only renders their stored results, since it runs on every frame.
+    fn refresh_checks(&mut self) {
+        self.check_tls_cert();
+        self.check_protocol_params();
+        self.check_key_permissions();
+        self.validate_options();
+    }
+
+    /// Recomputes what `view` shows about the options, the saved options and
+    /// the detected version; called whenever one of them changes.
+    fn refresh_derived(&mut self) {
+        self.unsaved_changes = self.saved_options.diff(&self.run_options);
+        self.detected_version = match &self.hydra_node_version {
+            Some(Ok(version)) => parse_version(version),
+            _ => None,
         };
+        self.unsupported_flags = self
+            .detected_version
+            .map(|version| unsupported_flags(version, &self.run_options.to_args()))
+            .unwrap_or_default();
+        (self.suggested_scripts_tx_id, self.chain_point_issue) =
+            match &self.run_options.chain_config {
+                ChainConfig::Direct(direct_config) => (
+                    known_scripts_tx_id(&direct_config.network_id, self.detected_version)
+                        .filter(|known| *known != direct_config.hydra_scripts_tx_id),
+                    direct_config
+                        .start_chain_from
+                        .as_deref()
+                        .and_then(check_chain_point),
+                ),
+                ChainConfig::Offline(_) => (None, None),
+            };
+    }
 
-        column![title, config_type, config_fields]
-            .spacing(10)
-            .into()
+    fn validate_options(&mut self) {
+        self.issues = self
+            .run_options
+            .validate_with_profiles(self.profiles.values());
     }
 
-    fn persistence_section(&self) -> Element<Message> {
-        let title = text("Persistence Settings").size(24);
+    fn check_protocol_params(&mut self) {
+        let path = &self
+            .run_options
+            .ledger_config
+            .cardano_ledger_protocol_parameters_file;
+        self.protocol_params_error = validate_protocol_params(path).err().map(|e| e.to_string());
+    }
 
-        let dir_path = row![
-            text("Persistence Directory:").width(Length::Fixed(150.0)),
-            text_input(
-                "Enter persistence directory path...",
-                &self.run_options.persistence_dir.to_string_lossy(),
-            )
-            .on_input(Message::PersistenceDirChanged),
-            button("Browse").on_press(Message::BrowsePersistenceDir)
-        ]
-        .spacing(10)
-        .align_y(alignment::Alignment::Center);
+    fn check_key_permissions(&mut self) {
+        let mut keys = vec![&self.run_options.hydra_signing_key];
+        if let ChainConfig::Direct(config) = &self.run_options.chain_config {
+            keys.push(&config.cardano_signing_key);
+        }
 
-        column![title, dir_path].spacing(10).into()
+        self.insecure_keys = keys
+            .into_iter()
+            .filter_map(|key| insecure_permissions(key).map(|mode| (key.clone(), mode)))
+            .collect();
     }
 
-    pub fn update(&mut self, message: Message) {
-        match message {
-            Message::VerbosityToggled(quiet) => {
-                self.run_options.verbosity = if quiet {
-                    Verbosity::Quiet
-                } else {
-                    Verbosity::Verbose
-                };
-            }
-            Message::NodeIdChanged(id) => {
-                self.run_options.node_id = NodeId(id);
-            }
-            Message::HostChanged(host) => {
-                if let Ok(ip) = host.parse() {
-                    self.run_options.host = ip;
-                }
-            }
-            Message::PortChanged(port) => {
-                if let Ok(p) = port.parse() {
-                    self.run_options.port = p;
-                }
-            }
-            Message::ApiHostChanged(host) => {
-                if let Ok(ip) = host.parse() {
-                    self.run_options.api_host = ip;
-                }
-            }
-            Message::ApiPortChanged(port) => {
-                if let Ok(p) = port.parse() {
-                    self.run_options.api_port = p;
-                }
-            }
-            Message::MonitoringPortChanged(port) => {
-                self.run_options.monitoring_port = port.parse().ok();
-            }
-            Message::TlsCertPathChanged(path) => {
-                self.run_options.tls_cert_path = Some(PathBuf::from(path));
-            }
-            Message::TlsKeyPathChanged(path) => {
-                self.run_options.tls_key_path = Some(PathBuf::from(path));
-            }
-            Message::HydraSigningKeyChanged(path) => {
-                self.run_options.hydra_signing_key = PathBuf::from(path);
-            }
-            Message::AddHydraVerificationKey => {
-                self.run_options
-                    .hydra_verification_keys
-                    .push(PathBuf::new());
-            }
-            Message::RemoveHydraVerificationKey(idx) => {
-                self.run_options.hydra_verification_keys.remove(idx);
-            }
-            Message::HydraVerificationKeyChanged(idx, path) => {
-                if let Some(key) = self.run_options.hydra_verification_keys.get_mut(idx) {
-                    *key = PathBuf::from(path);
-                }
-            }
-            Message::ChainConfigTypeChanged(config_type) => {
-                self.chain_config_type = config_type;
-                self.run_options.chain_config = match config_type {
-                    ChainConfigType::Direct => ChainConfig::Direct(DirectChainConfig {
-                        network_id: NetworkId::Testnet(42),
-                        node_socket: "node.socket".into(),
-                        hydra_scripts_tx_id: "".into(),
-                        cardano_signing_key: "cardano.sk".into(),
-                        cardano_verification_keys: Vec::new(),
-                        start_chain_from: None,
-                        contestation_period: 60,
-                        deposit_deadline: 60,
-                    }),
-                    ChainConfigType::Offline => ChainConfig::Offline(OfflineChainConfig {
-                        initial_utxo_file: "utxo.json".into(),
-                        ledger_genesis_file: None,
-                    }),
-                };
-            }
-            // TODO: handle network ID correctly
-            Message::NetworkIdChanged(_) => {
-                if let ChainConfig::Direct(config) = &mut self.run_options.chain_config {
-                    config.network_id = NetworkId::Mainnet
+    fn check_tls_cert(&mut self) {
+        self.cert_validity = self
+            .run_options
+            .tls_cert_path
+            .as_deref()
+            .and_then(CertValidity::load);
+    }
+
+    fn debounce(&mut self, field: DebouncedField) {
+        self.pending_inputs.insert(field, Instant::now());
+    }
+
+    fn spawn_node(&mut self) -> Task<Message> {
+        let _span = tracing::info_span!("launch_node").entered();
+
+        let log = if self.log_to_file {
+            let path = self.log_file_path();
+            match open_log_file(&path) {
+                Ok(file) => Some(file),
+                Err(e) => {
+                    tracing::error!(path = %path.display(), error = %e, "failed to open node log");
+                    self.status = Some(format!("Failed to open {}: {}", path.display(), e));
+                    return Task::none();
                 }
             }
-            Message::NodeSocketChanged(socket) => {
-                if let ChainConfig::Direct(config) = &mut self.run_options.chain_config {
-                    config.node_socket = PathBuf::from(socket);
+        } else {
+            None
+        };
+
+        let mut command = self.node_command();
+        command.stdout(Stdio::piped()).stderr(Stdio::piped());
+
+        match command.spawn() {
+            Ok(mut child) => {
+                tracing::info!(pid = child.id(), "hydra-node started");
+                self.status = Some(format!("hydra-node started (pid {})", child.id()));
+
+                let output = child.stdout.take().zip(child.stderr.take());
+                self.node_process = Some(child);
+
+                if let Some((stdout, stderr)) = output {
+                    return Task::run(node_output(stdout, stderr, log), Message::NodeLogLine);
                 }
             }
-            Message::PersistenceDirChanged(dir) => {
-                self.run_options.persistence_dir = PathBuf::from(dir);
+            Err(e) => {
+                tracing::error!(error = %e, "failed to launch hydra-node");
+                self.status = Some(format!("Failed to launch hydra-node: {}", e));
             }
+        }
 
-            Message::BrowseTlsCert => {
-                // TODO: Implement file dialog for TLS cert
+        Task::none()
+    }
+
+    fn log_file_path(&self) -> PathBuf {
+        if self.log_file.trim().is_empty() {
+            default_log_path(&self.run_options.persistence_dir)
+        } else {
+            PathBuf::from(self.log_file.trim())
+        }
+    }
+
+    fn node_command(&self) -> Command {
+        let mut command = Command::new(&self.hydra_node_binary);
+        command.args(Vec::<OsString>::from(&self.run_options));
+
+        for (key, value) in &self.extra_env {
+            if !key.is_empty() {
+                command.env(key, value);
             }
-            Message::BrowseTlsKey => {
-                // TODO: Implement file dialog for TLS key
+        }
+
+        command
+    }
+}
+
+fn write_initial_utxo(initial_utxo: Option<(PathBuf, String)>) -> Result<(), String> {
+    let Some((path, json)) = initial_utxo else {
+        return Ok(());
+    };
+
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+fn node_socket_status(path: &Path) -> Result<(), String> {
+    node_socket_problem(path).map_or(Ok(()), Err)
+}
+
+/// Indented `policy id.asset name → quantity` rows below a UTxO entry.
+fn utxo_assets_table<'a>(
+    idx: usize,
+    assets: &[(String, String, i64)],
+    enabled: bool,
+) -> Element<'a, Message> {
+    assets
+        .iter()
+        .enumerate()
+        .fold(
+            Column::new().spacing(5).padding([0, 40]),
+            |column, (asset_idx, (policy_id, name, quantity))| {
+                column.push(
+                    row![
+                        text_input("Policy ID", policy_id)
+                            .on_input_maybe(enabled.then_some(move |value| {
+                                Message::AssetChanged(idx, asset_idx, AssetField::PolicyId, value)
+                            }))
+                            .width(Length::FillPortion(3)),
+                        text("."),
+                        text_input("Asset name (hex)", name)
+                            .on_input_maybe(enabled.then_some(move |value| {
+                                Message::AssetChanged(idx, asset_idx, AssetField::AssetName, value)
+                            }))
+                            .width(Length::FillPortion(2)),
+                        text("→"),
+                        text_input("0", &quantity.to_string())
+                            .on_input_maybe(enabled.then_some(move |value| {
+                                Message::AssetChanged(idx, asset_idx, AssetField::Quantity, value)
+                            }))
+                            .width(Length::FillPortion(1)),
+                        button("Remove").on_press_maybe(enabled.then(|| {
+                            Message::RemoveAsset(idx, policy_id.clone(), name.clone())
+                        }))
+                    ]
+                    .spacing(10)
+                    .align_y(alignment::Alignment::Center),
+                )
+            },
+        )
+        .into()
+}
+
+/// Decrement and increment buttons for a port input. They dispatch the input's
+/// own change message, stop at ports 1 and 65535 (port 0 means "any free
+/// port" to the OS, not a port to listen on) and are disabled while the port
+/// cannot be edited.
+fn port_stepper<'a>(
+    port: Option<u16>,
+    on_change: fn(String) -> Message,
+    enabled: bool,
+) -> Element<'a, Message> {
+    let step = |next: fn(u16) -> Option<u16>| {
+        port.filter(|_| enabled)
+            .and_then(next)
+            .map(|port| on_change(port.to_string()))
+    };
+
+    row![
+        button(text("-")).on_press_maybe(step(|port| (port > 1).then(|| port - 1))),
+        button(text("+")).on_press_maybe(step(|port| (port < u16::MAX).then(|| port + 1)))
+    ]
+    .spacing(5)
+    .into()
+}
+
+/// Up and down buttons for a row of a reorderable list, disabled at the ends
+/// and while the list cannot be edited.
+fn reorder_buttons<'a>(
+    idx: usize,
+    len: usize,
+    up: fn(usize) -> Message,
+    down: fn(usize) -> Message,
+    enabled: bool,
+) -> Element<'a, Message> {
+    row![
+        button(text("↑")).on_press_maybe((enabled && idx > 0).then(|| up(idx))),
+        button(text("↓")).on_press_maybe((enabled && idx + 1 < len).then(|| down(idx)))
+    ]
+    .spacing(5)
+    .into()
+}
+
+/// Swaps the item at `idx` with the one before it; the first item stays put.
+fn move_up<T>(items: &mut [T], idx: usize) {
+    if idx > 0 && idx < items.len() {
+        items.swap(idx - 1, idx);
+    }
+}
+
+/// Swaps the item at `idx` with the one after it; the last item stays put.
+fn move_down<T>(items: &mut [T], idx: usize) {
+    if idx + 1 < items.len() {
+        items.swap(idx, idx + 1);
+    }
+}
+
+fn optional_path(path: &Option<PathBuf>) -> String {
+    path.as_ref()
+        .map_or(String::new(), |p| p.to_string_lossy().into_owned())
+}
+
+/// Makes `content` the target for dropped files while the cursor is over it.
+fn drop_target<'a>(
+    field: PathField,
+    content: impl Into<Element<'a, Message>>,
+) -> Element<'a, Message> {
+    mouse_area(content)
+        .on_enter(Message::PathFieldHovered(field))
+        .on_exit(Message::PathFieldLeft(field))
+        .into()
+}
+
+/// The kind of address typed into a UTxO entry, flagged when no output can
+/// be sent to it.
+fn address_badge<'a>(address: &str) -> Element<'a, Message> {
+    if address.is_empty() {
+        return Space::with_width(Length::Shrink).into();
+    }
+
+    let address_type = detect_address_type(address);
+    let badge = text(address_type.to_string()).size(12);
+    let badge = if address_type.can_hold_funds() {
+        badge
+    } else {
+        badge.style(text::danger)
+    };
+
+    container(badge)
+        .padding([2, 6])
+        .style(container::rounded_box)
+        .into()
+}
+
+/// Puts `value` on the clipboard; disabled while the field is empty.
+fn copy_button<'a>(value: &str) -> Element<'a, Message> {
+    button("Copy")
+        .on_press_maybe((!value.is_empty()).then(|| Message::CopyValue(value.to_string())))
+        .into()
+}
+
+fn free_port_button<'a>(
+    host: IpAddr,
+    on_change: fn(String) -> Message,
+    enabled: bool,
+) -> Element<'a, Message> {
+    button("Find Free")
+        .on_press_maybe(enabled.then_some(Message::FindFreePort(host, on_change)))
+        .into()
+}
+
+/// Sets a single field back to its `RunOptions::default()` value. Chain fields
+/// are only reset while the matching chain mode is active.
+fn reset_field(options: &mut RunOptions, field: FieldId) {
+    let defaults = &*DEFAULT_RUN_OPTIONS;
+
+    match field {
+        FieldId::NodeId => options.node_id = defaults.node_id.clone(),
+        FieldId::Host => options.host = defaults.host,
+        FieldId::Port => options.port = defaults.port,
+        FieldId::ApiHost => options.api_host = defaults.api_host,
+        FieldId::ApiPort => options.api_port = defaults.api_port,
+        FieldId::MonitoringPort => options.monitoring_port = defaults.monitoring_port,
+        FieldId::TlsCertPath => options.tls_cert_path = defaults.tls_cert_path.clone(),
+        FieldId::TlsKeyPath => options.tls_key_path = defaults.tls_key_path.clone(),
+        FieldId::HydraSigningKey => options.hydra_signing_key = defaults.hydra_signing_key.clone(),
+        FieldId::PersistenceDir => options.persistence_dir = defaults.persistence_dir.clone(),
+        FieldId::Verbosity => options.verbosity = defaults.verbosity.clone(),
+        FieldId::Peers => options.peers = defaults.peers.clone(),
+        FieldId::ChainConfigType => {
+            if !is_default_field(options, field) {
+                options.chain_config = defaults.chain_config.clone();
             }
-            Message::BrowseHydraSigningKey => {
-                // TODO: Implement file dialog for Hydra signing key
+        }
+        FieldId::HydraVerificationKeys => {
+            options.hydra_verification_keys = defaults.hydra_verification_keys.clone();
+        }
+        FieldId::ProtocolParametersFile => {
+            options.ledger_config = defaults.ledger_config.clone();
+        }
+        FieldId::MinimumContestationPeriod => {
+            options.hydra_protocol_params.minimum_contestation_period =
+                defaults.hydra_protocol_params.minimum_contestation_period;
+        }
+        FieldId::MaximumTxValidityRange => {
+            options.hydra_protocol_params.maximum_tx_validity_range =
+                defaults.hydra_protocol_params.maximum_tx_validity_range;
+        }
+        field => match (&mut options.chain_config, &defaults.chain_config) {
+            (ChainConfig::Direct(config), ChainConfig::Direct(default)) => {
+                reset_direct_field(config, default, field);
             }
-            Message::BrowseHydraVerificationKey(_idx) => {
-                // TODO: Implement file dialog for Hydra verification key
+            (ChainConfig::Offline(config), _) => {
+                reset_offline_field(config, &OfflineChainConfig::default(), field);
             }
-            Message::BrowsePersistenceDir => {
-                // TODO: Implement directory dialog
+            _ => {}
+        },
+    }
+}
+
+/// Whether `reset_field` would leave `options` unchanged.
+fn is_default_field(options: &RunOptions, field: FieldId) -> bool {
+    let defaults = &*DEFAULT_RUN_OPTIONS;
+
+    match field {
+        FieldId::NodeId => options.node_id == defaults.node_id,
+        FieldId::Host => options.host == defaults.host,
+        FieldId::Port => options.port == defaults.port,
+        FieldId::ApiHost => options.api_host == defaults.api_host,
+        FieldId::ApiPort => options.api_port == defaults.api_port,
+        FieldId::MonitoringPort => options.monitoring_port == defaults.monitoring_port,
+        FieldId::TlsCertPath => options.tls_cert_path == defaults.tls_cert_path,
+        FieldId::TlsKeyPath => options.tls_key_path == defaults.tls_key_path,
+        FieldId::HydraSigningKey => options.hydra_signing_key == defaults.hydra_signing_key,
+        FieldId::PersistenceDir => options.persistence_dir == defaults.persistence_dir,
+        FieldId::Verbosity => options.verbosity == defaults.verbosity,
+        FieldId::Peers => options.peers == defaults.peers,
+        FieldId::ChainConfigType => {
+            ChainConfigType::of(&options.chain_config)
+                == ChainConfigType::of(&defaults.chain_config)
+        }
+        FieldId::HydraVerificationKeys => {
+            options.hydra_verification_keys == defaults.hydra_verification_keys
+        }
+        FieldId::ProtocolParametersFile => options.ledger_config == defaults.ledger_config,
+        FieldId::MinimumContestationPeriod => {
+            options.hydra_protocol_params.minimum_contestation_period
+                == defaults.hydra_protocol_params.minimum_contestation_period
+        }
+        FieldId::MaximumTxValidityRange => {
+            options.hydra_protocol_params.maximum_tx_validity_range
+                == defaults.hydra_protocol_params.maximum_tx_validity_range
+        }
+        field => match (&options.chain_config, &defaults.chain_config) {
+            (ChainConfig::Direct(config), ChainConfig::Direct(default)) => {
+                is_default_direct_field(config, default, field)
             }
-            Message::SaveSettings => {
-                // TODO: Implement saving settings
-                println!("Saving settings: {:?}", self.run_options);
+            (ChainConfig::Offline(config), _) => {
+                is_default_offline_field(config, &OfflineChainConfig::default(), field)
             }
-            Message::InitialUtxoChanged(_) => todo!(),
-            Message::BrowseInitialUtxo => todo!(),
+            _ => true,
+        },
+    }
+}
+
+fn is_default_offline_field(
+    config: &OfflineChainConfig,
+    default: &OfflineChainConfig,
+    field: FieldId,
+) -> bool {
+    match field {
+        FieldId::InitialUtxoFile => config.initial_utxo_file == default.initial_utxo_file,
+        FieldId::LedgerGenesisFile => config.ledger_genesis_file == default.ledger_genesis_file,
+        _ => true,
+    }
+}
+
+fn reset_offline_field(
+    config: &mut OfflineChainConfig,
+    default: &OfflineChainConfig,
+    field: FieldId,
+) {
+    match field {
+        FieldId::InitialUtxoFile => config.initial_utxo_file = default.initial_utxo_file.clone(),
+        FieldId::LedgerGenesisFile => {
+            config.ledger_genesis_file = default.ledger_genesis_file.clone()
+        }
+        _ => {}
+    }
+}
+
+fn is_default_direct_field(
+    config: &DirectChainConfig,
+    default: &DirectChainConfig,
+    field: FieldId,
+) -> bool {
+    match field {
+        FieldId::NetworkId => config.network_id == default.network_id,
+        FieldId::NodeSocket => config.node_socket == default.node_socket,
+        FieldId::HydraScriptsTxId => config.hydra_scripts_tx_id == default.hydra_scripts_tx_id,
+        FieldId::CardanoSigningKey => config.cardano_signing_key == default.cardano_signing_key,
+        FieldId::StartChainFrom => config.start_chain_from == default.start_chain_from,
+        FieldId::ContestationPeriod => config.contestation_period == default.contestation_period,
+        FieldId::DepositDeadline => config.deposit_deadline == default.deposit_deadline,
+        FieldId::CardanoVerificationKeys => {
+            config.cardano_verification_keys == default.cardano_verification_keys
+        }
+        _ => true,
+    }
+}
+
+fn reset_direct_field(config: &mut DirectChainConfig, default: &DirectChainConfig, field: FieldId) {
+    match field {
+        FieldId::NetworkId => config.network_id = default.network_id.clone(),
+        FieldId::NodeSocket => config.node_socket = default.node_socket.clone(),
+        FieldId::HydraScriptsTxId => {
+            config.hydra_scripts_tx_id = default.hydra_scripts_tx_id.clone()
+        }
+        FieldId::CardanoSigningKey => {
+            config.cardano_signing_key = default.cardano_signing_key.clone()
         }
+        FieldId::StartChainFrom => config.start_chain_from = default.start_chain_from.clone(),
+        FieldId::ContestationPeriod => config.contestation_period = default.contestation_period,
+        FieldId::DepositDeadline => config.deposit_deadline = default.deposit_deadline,
+        FieldId::CardanoVerificationKeys => {
+            config.cardano_verification_keys = default.cardano_verification_keys.clone()
+        }
+        _ => {}
     }
 }
 
+/// Asks for a config file to open, for callers without a form yet.
+pub fn pick_config_file() -> Option<PathBuf> {
+    let mut app_config = AppConfig::load();
+    let path = app_config
+        .file_dialog(DialogPurpose::Config)
+        .add_filter("JSON", &["json"])
+        .pick_file()?;
+    app_config.remember_dialog_dir(DialogPurpose::Config, &path);
+    Some(path)
+}
+
 pub struct HydraSettingsApp {
     settings: HydraSettings,
 }
@@ -485,8 +4401,7 @@ impl HydraSettingsApp {
     }
 
     fn update(&mut self, message: Message) -> Task<Message> {
-        self.settings.update(message);
-        Task::none()
+        self.settings.update(message)
     }
 
     fn view(&self) -> Element<Message> {
@@ -496,3 +4411,174 @@ impl HydraSettingsApp {
             .into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Unlocked settings editing the default options, without reading any
+    /// saved state.
+    fn settings() -> HydraSettings {
+        let run_options = RunOptions::default();
+
+        HydraSettings {
+            chain_config_type: ChainConfigType::of(&run_options.chain_config),
+            run_options,
+            ..HydraSettings::default()
+        }
+    }
+
+    #[test]
+    fn chain_config_type_guard_accepts_matching_types() {
+        assert_eq!(settings().check_chain_config_type(), Ok(()));
+    }
+
+    #[test]
+    fn chain_config_type_guard_catches_a_desynced_picker() {
+        let mut settings = settings();
+        settings.run_options.chain_config = ChainConfig::Direct(DirectChainConfig::default());
+        settings.chain_config_type = ChainConfigType::Offline;
+
+        let error = settings.check_chain_config_type().unwrap_err();
+
+        assert!(
+            error.starts_with("Chain configuration is Online but Offline is selected"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn update_applies_simple_field_changes() {
+        let mut settings = settings();
+
+        let _ = settings.update(Message::PortChanged("5002".to_string()));
+        let _ = settings.update(Message::NodeIdChanged("bob".to_string()));
+        let _ = settings.update(Message::HostChanged("0.0.0.0".to_string()));
+
+        assert_eq!(settings.run_options.port, 5002);
+        assert_eq!(settings.run_options.node_id, NodeId("bob".to_string()));
+        assert_eq!(settings.run_options.host.to_string(), "0.0.0.0");
+    }
+
+    #[test]
+    fn update_keeps_the_port_and_shows_why_input_was_rejected() {
+        let mut settings = settings();
+
+        let _ = settings.update(Message::PortChanged("70000".to_string()));
+
+        assert_eq!(settings.run_options.port, RunOptions::default().port);
+        assert_eq!(
+            settings.invalid_inputs.get(&FieldId::Port),
+            Some(&("70000".to_string(), "Port must be 1–65535".to_string()))
+        );
+    }
+
+    #[test]
+    fn update_refuses_edits_while_locked() {
+        let mut settings = settings();
+        settings.locked = true;
+
+        let _ = settings.update(Message::PortChanged("5002".to_string()));
+
+        assert_eq!(settings.run_options.port, RunOptions::default().port);
+    }
+
+    #[test]
+    fn update_refreshes_unsaved_changes_and_version_checks() {
+        let mut settings = settings();
+
+        let _ = settings.update(Message::PortChanged("5002".to_string()));
+        let _ = settings.update(Message::MonitoringPortChanged("9000".to_string()));
+        assert_eq!(settings.unsaved_changes.len(), 2);
+        assert!(settings.unsupported_flags.is_empty());
+
+        let _ = settings.update(Message::HydraNodeBinaryResult(Ok("0.8.0".to_string())));
+        assert_eq!(settings.detected_version, Some((0, 8, 0)));
+        assert!(settings
+            .unsupported_flags
+            .iter()
+            .any(|warning| warning.contains("--monitoring-port")));
+    }
+
+    #[test]
+    fn move_up_keeps_the_first_item_in_place() {
+        let mut items = vec!["a", "b", "c"];
+
+        move_up(&mut items, 0);
+
+        assert_eq!(items, ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn move_down_keeps_the_last_item_in_place() {
+        let mut items = vec!["a", "b", "c"];
+
+        move_down(&mut items, 2);
+        move_down(&mut items, 3);
+
+        assert_eq!(items, ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn moves_swap_neighbours() {
+        let mut items = vec!["a", "b", "c"];
+
+        move_up(&mut items, 2);
+        assert_eq!(items, ["a", "c", "b"]);
+
+        move_down(&mut items, 0);
+        assert_eq!(items, ["c", "a", "b"]);
+    }
+
+    #[test]
+    fn resets_offline_ledger_and_protocol_fields() {
+        let mut options = RunOptions {
+            verbosity: Verbosity::Quiet,
+            chain_config: ChainConfig::Offline(OfflineChainConfig {
+                initial_utxo_file: "alice-utxo.json".into(),
+                ledger_genesis_file: Some("genesis.json".into()),
+            }),
+            hydra_protocol_params: HydraProtocolParams::mainnet(),
+            ..RunOptions::default()
+        };
+        options
+            .ledger_config
+            .cardano_ledger_protocol_parameters_file = "pp.json".into();
+        let fields = [
+            FieldId::Verbosity,
+            FieldId::InitialUtxoFile,
+            FieldId::LedgerGenesisFile,
+            FieldId::ProtocolParametersFile,
+            FieldId::MinimumContestationPeriod,
+            FieldId::MaximumTxValidityRange,
+        ];
+
+        for field in fields {
+            assert!(!is_default_field(&options, field), "{:?}", field);
+            reset_field(&mut options, field);
+            assert!(is_default_field(&options, field), "{:?}", field);
+        }
+
+        assert_eq!(
+            options,
+            RunOptions {
+                chain_config: ChainConfig::Offline(OfflineChainConfig::default()),
+                ..RunOptions::default()
+            }
+        );
+    }
+
+    #[test]
+    fn chain_config_type_is_highlighted_once_it_differs() {
+        let mut settings = settings();
+        assert!(settings.is_default(FieldId::ChainConfigType));
+
+        let _ = settings.update(Message::ChainConfigTypeChanged(ChainConfigType::Offline));
+        assert!(!settings.is_default(FieldId::ChainConfigType));
+
+        let _ = settings.update(Message::ResetField(FieldId::ChainConfigType));
+        assert!(settings.is_default(FieldId::ChainConfigType));
+        assert_eq!(settings.chain_config_type, ChainConfigType::Direct);
+    }
+}
@@ -0,0 +1,61 @@
+use iced::widget::text::Span;
+use iced::widget::{button, column, container, rich_text, scrollable, span, text, Column};
+use iced::{Color, Element, Font, Length};
+
+use crate::hydra::datum::Highlight;
+use crate::views::configure::Message;
+
+/// The inline datum of a UTxO entry, rendered and highlighted for preview.
+#[derive(Debug, Clone)]
+pub struct DatumPreview {
+    pub utxo_ref: String,
+    pub lines: Vec<Vec<(Highlight, String)>>,
+}
+
+/// The text color of each kind of highlighted piece; plain text keeps the
+/// theme's color.
+fn color(highlight: Highlight) -> Option<Color> {
+    match highlight {
+        Highlight::Plain => None,
+        Highlight::Node => Some(Color::from_rgb8(0x5e, 0x81, 0xf4)),
+        Highlight::Int => Some(Color::from_rgb8(0x12, 0x8a, 0x4a)),
+        Highlight::Bytes => Some(Color::from_rgb8(0xb4, 0x53, 0x09)),
+    }
+}
+
+pub fn view(preview: &DatumPreview) -> Element<Message> {
+    let lines = preview.lines.iter().fold(Column::new(), |column, pieces| {
+        let spans: Vec<Span<'_, (), Font>> = pieces
+            .iter()
+            .map(|(highlight, piece)| span(piece.as_str()).color_maybe(color(*highlight)))
+            .collect();
+        column.push(rich_text(spans).font(Font::MONOSPACE))
+    });
+
+    let dialog = container(
+        column![
+            text(format!("Inline datum of {}", preview.utxo_ref)).size(24),
+            scrollable(lines).height(Length::Fixed(400.0)),
+            button("Close").on_press(Message::CloseDatumPreview)
+        ]
+        .spacing(10),
+    )
+    .padding(20)
+    .max_width(900)
+    .style(container::rounded_box);
+
+    container(dialog)
+        .center_x(Length::Fill)
+        .center_y(Length::Fill)
+        .style(|_| container::Style {
+            background: Some(
+                Color {
+                    a: 0.6,
+                    ..Color::BLACK
+                }
+                .into(),
+            ),
+            ..container::Style::default()
+        })
+        .into()
+}
@@ -0,0 +1,156 @@
+use iced::widget::{button, checkbox, column, container, row, scrollable, text, Column};
+use iced::{Color, Element, Length};
+
+use crate::hydra::diff::RunOptionsDiff;
+use crate::hydra::run_options::{NodeId, RunOptions};
+use crate::views::configure::Message;
+
+/// A field that applying the incoming options would change.
+#[derive(Debug, Clone)]
+pub struct FieldDiff {
+    pub field: &'static str,
+    pub current: String,
+    pub new: String,
+}
+
+/// Where the incoming options come from.
+#[derive(Debug, Clone)]
+pub enum DiffSource {
+    Template,
+    Profile(NodeId),
+}
+
+/// Lists the fields other options would change and which of them to take.
+#[derive(Debug, Clone)]
+pub struct DiffModal {
+    pub diffs: Vec<FieldDiff>,
+    pub selections: Vec<bool>,
+    pub source: DiffSource,
+    diff: RunOptionsDiff,
+}
+
+impl DiffModal {
+    /// Every changed field starts out selected.
+    pub fn new(current: &RunOptions, incoming: &RunOptions, source: DiffSource) -> Self {
+        let diff = current.diff(incoming);
+        let diffs: Vec<FieldDiff> = diff
+            .rows()
+            .into_iter()
+            .map(|(field, current, new)| FieldDiff {
+                field,
+                current,
+                new,
+            })
+            .collect();
+
+        Self {
+            selections: vec![true; diffs.len()],
+            diffs,
+            source,
+            diff,
+        }
+    }
+
+    pub fn toggle(&mut self, idx: usize) {
+        if let Some(selected) = self.selections.get_mut(idx) {
+            *selected = !*selected;
+        }
+    }
+
+    pub fn all_selected(&self) -> bool {
+        !self.selections.contains(&false)
+    }
+
+    /// `current` with the selected fields taken from the incoming options.
+    pub fn apply_selected(&self, current: &RunOptions) -> RunOptions {
+        let fields: Vec<&str> = self
+            .diffs
+            .iter()
+            .zip(&self.selections)
+            .filter(|(_, selected)| **selected)
+            .map(|(diff, _)| diff.field)
+            .collect();
+
+        let mut options = current.clone();
+        self.diff.apply_fields(&mut options, &fields);
+        options
+    }
+}
+
+pub fn view(modal: &DiffModal) -> Element<Message> {
+    let title = match &modal.source {
+        DiffSource::Template => "Apply template".to_string(),
+        DiffSource::Profile(node_id) => format!("Switch to profile {}", node_id.0),
+    };
+
+    let header = row![
+        text("Field").width(Length::Fixed(220.0)),
+        text("Current").width(Length::Fill),
+        text("New").width(Length::Fill)
+    ]
+    .spacing(10);
+
+    let diffs = modal.diffs.iter().zip(&modal.selections).enumerate().fold(
+        Column::new().spacing(5),
+        |column, (idx, (diff, selected))| {
+            column.push(
+                row![
+                    checkbox(label(diff.field), *selected)
+                        .on_toggle(move |_| Message::ToggleDiffSelection(idx))
+                        .width(Length::Fixed(220.0)),
+                    text(&diff.current).width(Length::Fill),
+                    text(&diff.new).width(Length::Fill)
+                ]
+                .spacing(10),
+            )
+        },
+    );
+
+    let apply = modal
+        .selections
+        .contains(&true)
+        .then_some(Message::ApplySelectedDiffs);
+    let actions = row![
+        button("Apply Selected").on_press_maybe(apply),
+        button("Cancel").on_press(Message::CloseDiffModal)
+    ]
+    .spacing(10);
+
+    let dialog = container(
+        column![
+            text(title).size(24),
+            header,
+            scrollable(diffs).height(Length::Fixed(300.0)),
+            actions
+        ]
+        .spacing(10),
+    )
+    .padding(20)
+    .max_width(900)
+    .style(container::rounded_box);
+
+    container(dialog)
+        .center_x(Length::Fill)
+        .center_y(Length::Fill)
+        .style(|_| container::Style {
+            background: Some(
+                Color {
+                    a: 0.6,
+                    ..Color::BLACK
+                }
+                .into(),
+            ),
+            ..container::Style::default()
+        })
+        .into()
+}
+
+/// `node_id` as "Node id".
+fn label(field: &str) -> String {
+    let label = field.replace('_', " ");
+    let mut chars = label.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => label,
+    }
+}
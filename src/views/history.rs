@@ -0,0 +1,171 @@
+use std::collections::VecDeque;
+use std::mem::Discriminant;
+use std::time::{Duration, Instant};
+
+use crate::hydra::run_options::RunOptions;
+use crate::views::configure::Message;
+
+/// Snapshots kept for undo; the oldest are dropped first.
+const MAX_SNAPSHOTS: usize = 100;
+
+/// Edits of the same kind closer together than this are undone as one.
+const COALESCE_WINDOW: Duration = Duration::from_secs(1);
+
+/// Undo and redo stacks of settings snapshots.
+#[derive(Debug, Default)]
+pub struct History {
+    undo: VecDeque<RunOptions>,
+    redo: Vec<RunOptions>,
+    /// The kind of the last recorded edit and when it happened.
+    last_edit: Option<(Discriminant<Message>, Instant)>,
+}
+
+impl History {
+    /// Records the options as they were before an edit made by `edit`. A run
+    /// of the same edit, such as typing into one field, keeps only the
+    /// snapshot from before the first keystroke.
+    pub fn record(&mut self, before: RunOptions, edit: Discriminant<Message>) {
+        let now = Instant::now();
+        let coalesce = matches!(
+            self.last_edit,
+            Some((last, at)) if last == edit && now.duration_since(at) < COALESCE_WINDOW
+        );
+
+        self.last_edit = Some((edit, now));
+        self.redo.clear();
+
+        if !coalesce {
+            self.undo.push_back(before);
+            if self.undo.len() > MAX_SNAPSHOTS {
+                self.undo.pop_front();
+            }
+        }
+    }
+
+    /// Returns the snapshot to go back to, keeping `current` for redo.
+    pub fn undo(&mut self, current: RunOptions) -> Option<RunOptions> {
+        let previous = self.undo.pop_back()?;
+        self.redo.push(current);
+        self.last_edit = None;
+        Some(previous)
+    }
+
+    /// Returns the snapshot that was last undone, keeping `current` for undo.
+    pub fn redo(&mut self, current: RunOptions) -> Option<RunOptions> {
+        let next = self.redo.pop()?;
+        self.undo.push_back(current);
+        self.last_edit = None;
+        Some(next)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem;
+
+    use super::*;
+    use crate::hydra::run_options::NodeId;
+
+    fn with_port(port: u16) -> RunOptions {
+        RunOptions {
+            port,
+            ..RunOptions::default()
+        }
+    }
+
+    fn port_edit() -> Discriminant<Message> {
+        mem::discriminant(&Message::PortChanged(String::new()))
+    }
+
+    fn node_id_edit() -> Discriminant<Message> {
+        mem::discriminant(&Message::NodeIdChanged(String::new()))
+    }
+
+    #[test]
+    fn nothing_to_undo_or_redo_at_first() {
+        let mut history = History::default();
+
+        assert!(!history.can_undo());
+        assert!(!history.can_redo());
+        assert_eq!(history.undo(RunOptions::default()), None);
+        assert_eq!(history.redo(RunOptions::default()), None);
+    }
+
+    #[test]
+    fn coalesces_quick_edits_of_the_same_kind() {
+        let mut history = History::default();
+        history.record(with_port(5), port_edit());
+        history.record(with_port(50), port_edit());
+        history.record(with_port(500), port_edit());
+
+        assert_eq!(history.undo(with_port(5001)), Some(with_port(5)));
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn keeps_edits_of_different_kinds_apart() {
+        let mut history = History::default();
+        let renamed = RunOptions {
+            node_id: NodeId("bob".to_string()),
+            ..with_port(5002)
+        };
+        history.record(with_port(5001), port_edit());
+        history.record(with_port(5002), node_id_edit());
+
+        assert_eq!(history.undo(renamed.clone()), Some(with_port(5002)));
+        assert_eq!(history.undo(with_port(5002)), Some(with_port(5001)));
+        assert_eq!(history.redo(with_port(5001)), Some(with_port(5002)));
+        assert_eq!(history.redo(with_port(5002)), Some(renamed));
+    }
+
+    #[test]
+    fn undo_stops_coalescing() {
+        let mut history = History::default();
+        history.record(with_port(1), port_edit());
+        history.undo(with_port(2));
+        history.record(with_port(1), port_edit());
+
+        assert_eq!(history.undo(with_port(3)), Some(with_port(1)));
+    }
+
+    #[test]
+    fn new_edits_clear_redo() {
+        let mut history = History::default();
+        history.record(with_port(1), port_edit());
+        history.undo(with_port(2));
+        assert!(history.can_redo());
+
+        history.record(with_port(1), node_id_edit());
+        assert!(!history.can_redo());
+    }
+
+    #[test]
+    fn drops_the_oldest_snapshots() {
+        let mut history = History::default();
+        for port in 0..=MAX_SNAPSHOTS as u16 {
+            // Alternating kinds keeps every snapshot.
+            let edit = if port % 2 == 0 {
+                port_edit()
+            } else {
+                node_id_edit()
+            };
+            history.record(with_port(port), edit);
+        }
+
+        let mut oldest = None;
+        let mut current = RunOptions::default();
+        while let Some(previous) = history.undo(current) {
+            oldest = Some(previous.port);
+            current = previous;
+        }
+        assert_eq!(oldest, Some(1));
+    }
+}
@@ -0,0 +1,72 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use iced::widget::{button, column, container, row, text};
+use iced::{alignment, Element, Length};
+
+use super::configure::Message;
+use crate::hydra::keys::TextEnvelope;
+
+/// The `type` and `description` of a key file in the Cardano text envelope format.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct KeyPreview {
+    pub key_type: String,
+    pub description: String,
+}
+
+#[derive(Debug)]
+pub enum KeyPreviewError {
+    Io(io::Error),
+    UnknownFormat,
+}
+
+impl fmt::Display for KeyPreviewError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyPreviewError::Io(e) => write!(f, "Failed to read key file: {}", e),
+            KeyPreviewError::UnknownFormat => write!(f, "Unknown format"),
+        }
+    }
+}
+
+impl std::error::Error for KeyPreviewError {}
+
+impl KeyPreview {
+    pub fn load(path: &Path) -> Result<KeyPreview, KeyPreviewError> {
+        let contents = fs::read_to_string(path).map_err(KeyPreviewError::Io)?;
+        let envelope: TextEnvelope =
+            serde_json::from_str(&contents).map_err(|_| KeyPreviewError::UnknownFormat)?;
+
+        Ok(KeyPreview {
+            key_type: envelope.key_type,
+            description: envelope.description,
+        })
+    }
+}
+
+pub fn view<'a>(
+    path: &'a PathBuf,
+    preview: &'a Result<KeyPreview, KeyPreviewError>,
+) -> Element<'a, Message> {
+    let details = match preview {
+        Ok(preview) => column![
+            text(format!("Type: {}", preview.key_type)),
+            text(format!("Description: {}", preview.description))
+        ],
+        Err(e) => column![text(e.to_string())],
+    };
+
+    let header = row![
+        text(path.display().to_string()).width(Length::Fill),
+        button("Close").on_press(Message::CloseKeyPreview)
+    ]
+    .spacing(10)
+    .align_y(alignment::Alignment::Center);
+
+    container(column![header, details.spacing(5)].spacing(10))
+        .padding(10)
+        .style(container::rounded_box)
+        .into()
+}
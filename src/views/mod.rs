@@ -1 +1,7 @@
+pub mod changelog;
 pub mod configure;
+pub mod datum_modal;
+pub mod diff_modal;
+pub mod history;
+pub mod key_preview;
+pub mod welcome;
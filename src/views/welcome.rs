@@ -0,0 +1,66 @@
+use std::fmt;
+
+use iced::widget::{button, column, container, row, text, Row};
+use iced::{Alignment, Element, Length};
+
+use crate::hydra::example::example_run_options;
+use crate::hydra::run_options::{ChainConfig, OfflineChainConfig, RunOptions};
+use crate::views::configure::Message;
+
+/// Starting points offered to first-time users.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Template {
+    /// A two-peer head on the preprod testnet.
+    Preprod,
+    /// A single node running without a Cardano node.
+    Offline,
+}
+
+impl Template {
+    pub const ALL: [Template; 2] = [Template::Preprod, Template::Offline];
+
+    pub fn run_options(self) -> RunOptions {
+        match self {
+            Template::Preprod => example_run_options(),
+            Template::Offline => RunOptions {
+                chain_config: ChainConfig::Offline(OfflineChainConfig::default()),
+                ..RunOptions::default()
+            },
+        }
+    }
+}
+
+impl fmt::Display for Template {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Template::Preprod => write!(f, "Preprod head"),
+            Template::Offline => write!(f, "Offline node"),
+        }
+    }
+}
+
+pub fn view<'a>() -> Element<'a, Message> {
+    let templates = Template::ALL
+        .iter()
+        .fold(Row::new().spacing(10), |templates, template| {
+            templates
+                .push(button(text(template.to_string())).on_press(Message::UseTemplate(*template)))
+        });
+
+    container(
+        column![
+            text("Welcome to Juno").size(32),
+            text("Juno configures and runs a hydra-node. How would you like to start?"),
+            button("Start from scratch").on_press(Message::StartFromScratch),
+            button("Import existing config").on_press(Message::ImportConfig),
+            row![text("Use a template:"), templates]
+                .spacing(10)
+                .align_y(Alignment::Center),
+        ]
+        .spacing(20)
+        .align_x(Alignment::Center),
+    )
+    .center_x(Length::Fill)
+    .center_y(Length::Fill)
+    .into()
+}